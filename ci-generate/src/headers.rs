@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use clap::{ArgMatches, Parser};
+use figment::Figment;
+use serde::{Deserialize, Serialize};
+
+use generate_ci::headers::{update_headers, HeaderStatus, SpdxHeader};
+
+use crate::toolchains::retrieve_data;
+use crate::{current_year, from_id, git_authors};
+
+#[derive(Parser, Debug, Serialize, Deserialize)]
+pub(crate) struct HeadersData {
+    /// License written in the headers, also as an SPDX expression, e.g. "MIT OR Apache-2.0"
+    #[clap(long, short, value_parser = from_id, default_value = "MIT")]
+    license: String,
+    /// Copyright holder in the `Name Surname <email>` form, it can be repeated
+    #[clap(long = "author")]
+    #[serde(default = "git_authors", skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    /// Year of the copyright notices (default: the current year)
+    #[clap(long)]
+    #[serde(default = "current_year")]
+    year: Option<u16>,
+    /// Only check the headers, failing when some of them are missing or outdated
+    #[clap(long)]
+    #[serde(default)]
+    check: bool,
+    /// Path to the repository
+    #[clap(value_hint = clap::ValueHint::DirPath)]
+    project_path: PathBuf,
+}
+
+pub(crate) fn headers_config(config: Figment, matches: &ArgMatches) -> anyhow::Result<()> {
+    let headers = retrieve_data::<HeadersData>(config, matches, "headers")?;
+    if headers.authors.is_empty() {
+        anyhow::bail!("No copyright holder, set one through --author");
+    }
+
+    let year = headers
+        .year
+        .map(|year| format!("{year} "))
+        .unwrap_or_default();
+    let header = headers
+        .authors
+        .iter()
+        .fold(SpdxHeader::new(&headers.license), |header, author| {
+            header.copyright(&format!("{year}{author}"))
+        });
+
+    let mut outdated = 0;
+    for (path, status) in update_headers(&headers.project_path, &header, headers.check)? {
+        let path = path.display();
+        match (status, headers.check) {
+            (HeaderStatus::Current, _) => {}
+            (HeaderStatus::Unsupported, _) => println!("{path}: unknown comment syntax, skipped"),
+            (HeaderStatus::Missing, true) => println!("{path}: missing header"),
+            (HeaderStatus::Outdated, true) => println!("{path}: outdated header"),
+            (HeaderStatus::Missing, false) => println!("{path}: header added"),
+            (HeaderStatus::Outdated, false) => println!("{path}: header updated"),
+            (HeaderStatus::Conflict, _) => println!("{path}: another license is declared, skipped"),
+        }
+        if matches!(status, HeaderStatus::Missing | HeaderStatus::Outdated) {
+            outdated += 1;
+        }
+    }
+
+    if headers.check && outdated > 0 {
+        anyhow::bail!("{outdated} files have missing or outdated headers");
+    }
+    Ok(())
+}
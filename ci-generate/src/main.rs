@@ -1,6 +1,11 @@
+mod headers;
+mod reuse;
 mod toolchains;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::anyhow;
 use clap::parser::ValueSource;
@@ -15,6 +20,11 @@ use clap::CommandFactory;
 use figment::providers::{Format, Toml};
 use figment::Figment;
 
+use generate_ci::{
+    spdx::{CustomLicense, LicenseExpression},
+    ReuseFormat, TemplateData,
+};
+
 use tracing_subscriber::EnvFilter;
 
 use toolchains::*;
@@ -36,6 +46,10 @@ enum Cmd {
     #[cfg(feature = "yarn")]
     /// Generate a new yarn project.
     Yarn(CommonData),
+    /// Add or update the SPDX headers of the files in a repository.
+    Headers(headers::HeadersData),
+    /// Check whether a project complies with the REUSE specification.
+    ReuseLint(reuse::ReuseLintData),
 }
 
 fn choose_commands(config: Figment, sub: (&str, &ArgMatches)) -> anyhow::Result<()> {
@@ -50,6 +64,8 @@ fn choose_commands(config: Figment, sub: (&str, &ArgMatches)) -> anyhow::Result<
         ("poetry", matches) => poetry::poetry_config(config, matches),
         #[cfg(feature = "yarn")]
         ("yarn", matches) => yarn::yarn_config(config, matches),
+        ("headers", matches) => headers::headers_config(config, matches),
+        ("reuse-lint", matches) => reuse::reuse_lint(matches),
         _ => unreachable!("unexpected command"),
     }
 }
@@ -58,13 +74,29 @@ static DEFAULT_CONF: &str = r#"
     [default]
     license = "MIT"
     branch = "main"
+    reuse_format = "toml"
+    spdx_headers = false
 
     [meson]
     kind = "c"
+    warning_level = 3
+    werror = false
+    flags = ["-pedantic"]
+    layout = "both"
+
+    [maven]
+    java = 17
+    junit = 5
+    plugins = ["checkstyle", "jacoco"]
+    release = false
 
     [cargo]
     lib = false
     ci = false
+    release = false
+    docker_build = "multi-stage"
+    docker_registry = "ghcr"
+    docker_tags = ["semver", "sha", "latest"]
 "#;
 
 #[derive(Parser, Debug)]
@@ -80,27 +112,185 @@ struct Opts {
 }
 
 fn from_id(id: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    id.parse::<&dyn license::License>()
-        .map(|_| id.to_owned())
-        .map_err(|_| "License not found".into())
+    id.parse::<LicenseExpression>()
+        .map(|expression| expression.id().to_owned())
+        .or_else(|e| match e {
+            // Custom licenses are checked once their texts are known
+            generate_ci::error::Error::UnknownLicenseRef(_) => Ok(id.trim().to_owned()),
+            generate_ci::error::Error::InvalidLicense(_) => Err("License not found".into()),
+            e => Err(e.into()),
+        })
+}
+
+fn license_file(s: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s.split_once('=') {
+        Some((id, path)) if !id.is_empty() && !path.is_empty() => Ok(s.to_owned()),
+        _ => Err(format!("{s} is not in the LicenseRef-<name>=PATH form").into()),
+    }
+}
+
+fn reuse_format(
+    s: &str,
+) -> Result<ReuseFormat, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "dep5" => Ok(ReuseFormat::Dep5),
+        "toml" => Ok(ReuseFormat::Toml),
+        _ => Err(format!("{s} is not a valid REUSE format.").into()),
+    }
+}
+
+/// A custom license defined in the configuration file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CustomLicenseConfig {
+    /// File containing the license text
+    file: Option<PathBuf>,
+    /// License text
+    text: Option<String>,
+    /// Header quoted in source files
+    header: Option<String>,
+}
+
+impl CustomLicenseConfig {
+    fn custom_license(&self, id: &str) -> anyhow::Result<CustomLicense> {
+        let license = match (&self.file, &self.text) {
+            (Some(file), None) => CustomLicense::from_file(id, file)?,
+            (None, Some(text)) => CustomLicense::new(id, text)?,
+            _ => anyhow::bail!("{id} requires either a file or a text"),
+        };
+        Ok(match &self.header {
+            Some(header) => license.header(header),
+            None => license,
+        })
+    }
 }
 
 #[derive(Parser, Debug, Serialize, Deserialize)]
 struct CommonData {
-    /// License to be used in the project
+    /// License to be used in the project, also as an SPDX expression, e.g. "MIT OR Apache-2.0"
     #[clap(long, short, value_parser = from_id, default_value = "MIT")]
     license: String,
     /// GitHub branch name to be used in the project
     #[clap(long, short = 'b', default_value = "main")]
     branch: String,
+    /// Text of a custom license in the `LicenseRef-<name>=PATH` form, it can be repeated
+    #[clap(long = "license-file", value_parser = license_file)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    license_files: Vec<String>,
+    /// Format of the REUSE licensing information: toml (REUSE.toml) or dep5 (.reuse/dep5)
+    #[clap(long, value_parser = reuse_format, default_value = "toml")]
+    reuse_format: ReuseFormat,
+    /// Write SPDX headers at the top of the generated files
+    #[clap(long)]
+    spdx_headers: bool,
+    /// Custom licenses defined in the configuration file
+    #[clap(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    custom_licenses: HashMap<String, CustomLicenseConfig>,
     /// Project name
     #[clap(long)]
     name: String,
+    /// Project description
+    #[clap(long)]
+    description: Option<String>,
+    /// Project author in the `Name Surname <email>` form, it can be repeated
+    #[clap(long = "author")]
+    #[serde(default = "git_authors", skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    /// Project homepage
+    #[clap(long)]
+    homepage: Option<String>,
+    /// Project repository URL
+    #[clap(long)]
+    repository: Option<String>,
+    /// Project version
+    #[clap(long)]
+    version: Option<String>,
+    /// Year of the copyright notices (default: the current year)
+    #[clap(long)]
+    #[serde(default = "current_year")]
+    year: Option<u16>,
     /// Path to the new project
     #[clap(value_hint = clap::ValueHint::DirPath)]
     project_path: PathBuf,
 }
 
+impl CommonData {
+    fn template_data(&self) -> anyhow::Result<TemplateData<'_>> {
+        let mut data = TemplateData::new(&self.project_path, &self.name)
+            .branch(&self.branch)
+            .license(&self.license)
+            .reuse_format(self.reuse_format);
+        if self.spdx_headers {
+            data = data.spdx_headers();
+        }
+        // Licenses given on the command line take priority over the ones
+        // contained in the configuration file
+        for license_file in &self.license_files {
+            // The form has already been checked by the parser
+            let (id, path) = license_file.split_once('=').unwrap_or_default();
+            data = data.custom_license(CustomLicense::from_file(id, path.as_ref())?);
+        }
+        for (id, license) in &self.custom_licenses {
+            data = data.custom_license(license.custom_license(id)?);
+        }
+        if let Some(description) = &self.description {
+            data = data.description(description);
+        }
+        for author in &self.authors {
+            data = data.author(author);
+        }
+        if let Some(homepage) = &self.homepage {
+            data = data.homepage(homepage);
+        }
+        if let Some(repository) = &self.repository {
+            data = data.repository(repository);
+        }
+        if let Some(version) = &self.version {
+            data = data.version(version);
+        }
+        if let Some(year) = self.year {
+            data = data.year(year);
+        }
+        Ok(data)
+    }
+}
+
+// Retrieve the default author from the git configuration
+fn git_authors() -> Vec<String> {
+    let git_config = |key| {
+        Command::new("git")
+            .args(["config", key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+    };
+
+    match (git_config("user.name"), git_config("user.email")) {
+        (Some(name), Some(email)) => vec![format!("{name} <{email}>")],
+        (Some(name), None) => vec![name],
+        _ => Vec::new(),
+    }
+}
+
+// Retrieve the current year from the system clock
+fn current_year() -> Option<u16> {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let mut days = seconds / 86_400;
+    let mut year = 1970;
+    loop {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let year_days = if leap { 366 } else { 365 };
+        if days < year_days {
+            return Some(year);
+        }
+        days -= year_days;
+        year += 1;
+    }
+}
+
 struct ClapSerialized<T> {
     serialized: Serialized<T>,
     matches: ArgMatches,
@@ -147,6 +337,10 @@ impl<T: Serialize> Provider for ClapSerialized<T> {
                 dict.remove(id);
             });
 
+        // Optional arguments not passed on the command line must not override
+        // the values contained in the configuration file
+        dict.retain(|_, value| !matches!(value, Value::Empty(..)));
+
         let value = Value::Dict(tag, dict);
         let dict = match &self.serialized.key {
             Some(key) => figment::util::nest(key, value).into_dict().ok_or(error)?,
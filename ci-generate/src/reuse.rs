@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use clap::{ArgMatches, FromArgMatches, Parser};
+
+use generate_ci::reuse::lint;
+
+#[derive(Parser, Debug)]
+pub(crate) struct ReuseLintData {
+    /// Path to the project
+    #[clap(value_hint = clap::ValueHint::DirPath, default_value = ".")]
+    project_path: PathBuf,
+}
+
+pub(crate) fn reuse_lint(matches: &ArgMatches) -> anyhow::Result<()> {
+    let data = ReuseLintData::from_arg_matches(matches)?;
+    let report = lint(&data.project_path)?;
+    println!("{report}");
+    if !report.is_compliant() {
+        anyhow::bail!("The project is not REUSE compliant");
+    }
+    Ok(())
+}
@@ -3,8 +3,8 @@ use clap::{ArgMatches, Parser};
 use figment::Figment;
 use serde::{Deserialize, Serialize};
 
-use generate_ci::cargo::Cargo;
-use generate_ci::{CreateCi, TemplateData};
+use generate_ci::cargo::{Cargo, DockerBuild, ImageTag, Registry};
+use generate_ci::CreateCi;
 
 use crate::CommonData;
 
@@ -15,6 +15,24 @@ pub(crate) struct CargoData {
     /// Docker image description.
     #[clap(long)]
     docker_image_description: Option<String>,
+    /// Minimum supported Rust version, e.g. 1.70
+    #[clap(long, value_parser = rust_version)]
+    msrv: Option<String>,
+    /// How the Docker image is built: multi-stage or prebuilt
+    #[clap(long, value_parser = docker_build, default_value = "multi-stage")]
+    docker_build: DockerBuild,
+    /// Registry where the Docker image is pushed: ghcr, docker-hub, quay or a custom host
+    #[clap(long, default_value = "ghcr")]
+    docker_registry: String,
+    /// Docker image name, e.g. organization/project
+    #[clap(long)]
+    docker_image_name: Option<String>,
+    /// Tags assigned to the Docker image: semver, sha or latest
+    #[clap(long = "docker-tag", value_parser = image_tag, default_values = ["semver", "sha", "latest"])]
+    docker_tags: Vec<ImageTag>,
+    /// Used for publishing binaries through a release workflow
+    #[clap(long, global = false)]
+    release: bool,
     /// Used for creating a library project
     #[clap(long, global = false)]
     lib: bool,
@@ -26,28 +44,73 @@ pub(crate) struct CargoData {
     common: CommonData,
 }
 
+fn rust_version(s: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let parts: Vec<&str> = s.split('.').collect();
+    if (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+    {
+        Ok(s.to_owned())
+    } else {
+        Err(format!("{s} is not a valid Rust version.").into())
+    }
+}
+
+fn docker_build(
+    s: &str,
+) -> Result<DockerBuild, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "multi-stage" => Ok(DockerBuild::MultiStage),
+        "prebuilt" => Ok(DockerBuild::Prebuilt),
+        _ => Err(format!("{s} is not a valid Docker build mode.").into()),
+    }
+}
+
+fn image_tag(s: &str) -> Result<ImageTag, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "semver" => Ok(ImageTag::Semver),
+        "sha" => Ok(ImageTag::Sha),
+        "latest" => Ok(ImageTag::Latest),
+        _ => Err(format!("{s} is not a valid Docker image tag.").into()),
+    }
+}
+
+fn registry(s: &str) -> Registry<'_> {
+    match s {
+        "ghcr" => Registry::Ghcr,
+        "docker-hub" => Registry::DockerHub,
+        "quay" => Registry::Quay,
+        host => Registry::Custom(host),
+    }
+}
+
 pub(crate) fn cargo_config(config: Figment, matches: &ArgMatches) -> anyhow::Result<()> {
     let cargo = retrieve_data::<CargoData>(config, matches, "cargo")?;
     let docker_image_description = cargo.docker_image_description.map_or_else(
         || format!("{} description", &cargo.common.name),
         |desc| desc,
     );
-    let data = TemplateData::new(&cargo.common.project_path, &cargo.common.name)
-        .branch(&cargo.common.branch)
-        .license(&cargo.common.license);
+    let data = cargo.common.template_data()?;
+    let mut generator = Cargo::new()
+        .docker_image_description(&docker_image_description)
+        .docker_build(cargo.docker_build)
+        .docker_registry(registry(&cargo.docker_registry))
+        .docker_tags(&cargo.docker_tags);
+    if let Some(docker_image_name) = &cargo.docker_image_name {
+        generator = generator.docker_image_name(docker_image_name);
+    }
+    if let Some(msrv) = &cargo.msrv {
+        generator = generator.msrv(msrv);
+    }
+    if cargo.release {
+        generator = generator.release_binaries();
+    }
     if cargo.ci {
-        Ok(Cargo::new()
-            .docker_image_description(&docker_image_description)
-            .only_ci()
-            .create_ci(data)?)
+        Ok(generator.only_ci().create_ci(data)?)
     } else if !cargo.ci && cargo.lib {
-        Ok(Cargo::new()
-            .docker_image_description(&docker_image_description)
-            .create_lib()
-            .create_ci(data)?)
+        Ok(generator.create_lib().create_ci(data)?)
     } else {
-        Ok(Cargo::new()
-            .docker_image_description(&docker_image_description)
-            .create_ci(data)?)
+        Ok(generator.create_ci(data)?)
     }
 }
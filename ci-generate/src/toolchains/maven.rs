@@ -3,8 +3,8 @@ use clap::{ArgMatches, Parser};
 use figment::Figment;
 use serde::{Deserialize, Serialize};
 
-use generate_ci::maven::Maven;
-use generate_ci::{CreateProject, TemplateData};
+use generate_ci::maven::{JUnit, JavaVersion, Maven, MavenPlugin};
+use generate_ci::CreateProject;
 
 use crate::CommonData;
 
@@ -14,15 +14,119 @@ use super::retrieve_data;
 pub(crate) struct MavenData {
     /// Java group.
     group: String,
+    /// Targeted Java release: 11, 17 or 21
+    #[clap(long, value_parser = java_version, default_value = "17")]
+    java: JavaVersion,
+    /// JUnit version used by the tests: 4 or 5
+    #[clap(long, value_parser = junit, default_value = "5")]
+    junit: JUnit,
+    /// Quality plugins: checkstyle, spotless, spotbugs, pmd or jacoco
+    #[clap(long = "plugin", value_parser = plugin, default_values = ["checkstyle", "jacoco"])]
+    plugins: Vec<MavenPlugin>,
+    /// Minimum line coverage checked by jacoco, as a percentage
+    #[clap(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    coverage_threshold: Option<u8>,
+    /// Modules of a multi-module project
+    #[clap(long = "module")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    modules: Vec<String>,
+    /// Used for deploying signed artifacts through a release workflow
+    #[clap(long)]
+    release: bool,
+    /// Repository the release workflow deploys to, Maven Central by default
+    #[clap(long)]
+    release_repository: Option<String>,
     #[clap(flatten)]
     #[serde(flatten)]
     common: CommonData,
 }
 
+fn java_version(
+    s: &str,
+) -> Result<JavaVersion, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let version = s
+        .parse::<u8>()
+        .map_err(|_| format!("{s} is not a Java release."))?;
+    Ok(JavaVersion::try_from(version)?)
+}
+
+fn junit(s: &str) -> Result<JUnit, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let version = s
+        .parse::<u8>()
+        .map_err(|_| format!("{s} is not a JUnit version."))?;
+    Ok(JUnit::try_from(version)?)
+}
+
+fn plugin(s: &str) -> Result<MavenPlugin, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "checkstyle" => Ok(MavenPlugin::Checkstyle),
+        "spotless" => Ok(MavenPlugin::Spotless),
+        "spotbugs" => Ok(MavenPlugin::SpotBugs),
+        "pmd" => Ok(MavenPlugin::Pmd),
+        "jacoco" => Ok(MavenPlugin::Jacoco),
+        _ => Err(format!("{s} is not a valid maven plugin.").into()),
+    }
+}
+
 pub(crate) fn maven_config(config: Figment, matches: &ArgMatches) -> anyhow::Result<()> {
     let maven = retrieve_data::<MavenData>(config, matches, "maven")?;
-    let data = TemplateData::new(&maven.common.project_path, &maven.common.name)
-        .branch(&maven.common.branch)
-        .license(&maven.common.license);
-    Ok(Maven::new().group(&maven.group).create_project(data)?)
+    let data = maven.common.template_data()?;
+    let modules: Vec<&str> = maven.modules.iter().map(String::as_str).collect();
+    let mut generator = Maven::new()
+        .group(&maven.group)
+        .java(maven.java)
+        .junit(maven.junit)
+        .plugins(&maven.plugins)
+        .modules(&modules);
+    if let Some(coverage_threshold) = maven.coverage_threshold {
+        generator = generator.coverage_threshold(coverage_threshold);
+    }
+    if maven.release {
+        generator = generator.release();
+    }
+    if let Some(release_repository) = &maven.release_repository {
+        generator = generator.release_repository(release_repository);
+    }
+    Ok(generator.create_project(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+    use figment::providers::{Format, Toml};
+
+    use super::*;
+
+    #[test]
+    fn maven_default_options() {
+        let matches = crate::Opts::command().get_matches_from([
+            "ci-generate",
+            "maven",
+            "--name",
+            "maven-project",
+            "org.example",
+            "maven-project",
+        ]);
+        let (_, matches) = matches.subcommand().unwrap();
+        let config = Figment::new().merge(Toml::string(crate::DEFAULT_CONF).nested());
+
+        let maven = retrieve_data::<MavenData>(config, matches, "maven").unwrap();
+        assert!(!maven.release);
+        assert!(maven.release_repository.is_none());
+    }
+
+    #[test]
+    fn maven_coverage_threshold() {
+        let matches = crate::Opts::command().try_get_matches_from([
+            "ci-generate",
+            "maven",
+            "--coverage-threshold",
+            "150",
+            "--name",
+            "maven-project",
+            "org.example",
+            "maven-project",
+        ]);
+        assert!(matches.is_err());
+    }
 }
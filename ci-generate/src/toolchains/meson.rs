@@ -3,8 +3,8 @@ use clap::{ArgMatches, Parser};
 use figment::Figment;
 use serde::{Deserialize, Serialize};
 
-use generate_ci::meson::{Meson, ProjectKind};
-use generate_ci::{CreateProject, TemplateData};
+use generate_ci::meson::{Layout, LibraryKind, Meson, ProjectKind, Standard, TestFramework};
+use generate_ci::CreateProject;
 
 use crate::CommonData;
 
@@ -15,6 +15,30 @@ pub(crate) struct MesonData {
     /// Kind of a new meson project
     #[clap(long, short, value_parser = project_kind, default_value = "c")]
     kind: ProjectKind,
+    /// Language standard: c11, c17, c23, c++17, c++20 or c++23
+    #[clap(long = "std", value_parser = standard)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    standard: Option<Standard>,
+    /// Warning level, from 0 to 3
+    #[clap(long, value_parser = clap::value_parser!(u8).range(0..=3), default_value = "3")]
+    warning_level: u8,
+    /// Treat warnings as errors
+    #[clap(long)]
+    werror: bool,
+    /// Compiler flags, used when supported by the compiler
+    #[clap(long = "flag", allow_hyphen_values = true, default_values = ["-pedantic"])]
+    flags: Vec<String>,
+    /// Unit-test framework: unity, criterion, gtest or catch2
+    #[clap(long, value_parser = test_framework)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    test_framework: Option<TestFramework>,
+    /// Targets to build: library, executable or both
+    #[clap(long, value_parser = layout, default_value = "both")]
+    layout: Layout,
+    /// Kind of the library: shared, static or both
+    #[clap(long, value_parser = library_kind)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    library_kind: Option<LibraryKind>,
     #[clap(flatten)]
     #[serde(flatten)]
     common: CommonData,
@@ -30,10 +54,70 @@ fn project_kind(
     }
 }
 
+fn standard(s: &str) -> Result<Standard, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "c11" => Ok(Standard::C11),
+        "c17" => Ok(Standard::C17),
+        "c23" => Ok(Standard::C23),
+        "c++17" => Ok(Standard::Cxx17),
+        "c++20" => Ok(Standard::Cxx20),
+        "c++23" => Ok(Standard::Cxx23),
+        _ => Err(format!("{s} is not a valid language standard.").into()),
+    }
+}
+
+fn test_framework(
+    s: &str,
+) -> Result<TestFramework, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "unity" => Ok(TestFramework::Unity),
+        "criterion" => Ok(TestFramework::Criterion),
+        "gtest" => Ok(TestFramework::GoogleTest),
+        "catch2" => Ok(TestFramework::Catch2),
+        _ => Err(format!("{s} is not a valid test framework.").into()),
+    }
+}
+
+fn layout(s: &str) -> Result<Layout, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "library" => Ok(Layout::Library),
+        "executable" => Ok(Layout::Executable),
+        "both" => Ok(Layout::Both),
+        _ => Err(format!("{s} is not a valid meson project layout.").into()),
+    }
+}
+
+fn library_kind(
+    s: &str,
+) -> Result<LibraryKind, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s {
+        "shared" => Ok(LibraryKind::Shared),
+        "static" => Ok(LibraryKind::Static),
+        "both" => Ok(LibraryKind::Both),
+        _ => Err(format!("{s} is not a valid library kind.").into()),
+    }
+}
+
 pub(crate) fn meson_config(config: Figment, matches: &ArgMatches) -> anyhow::Result<()> {
     let meson = retrieve_data::<MesonData>(config, matches, "meson")?;
-    let data = TemplateData::new(&meson.common.project_path, &meson.common.name)
-        .branch(&meson.common.branch)
-        .license(&meson.common.license);
-    Ok(Meson::new().kind(meson.kind).create_project(data)?)
+    let data = meson.common.template_data()?;
+    let flags: Vec<&str> = meson.flags.iter().map(String::as_str).collect();
+    let mut generator = Meson::new()
+        .kind(meson.kind)
+        .warning_level(meson.warning_level)
+        .flags(&flags)
+        .layout(meson.layout);
+    if let Some(standard) = meson.standard {
+        generator = generator.standard(standard);
+    }
+    if meson.werror {
+        generator = generator.werror();
+    }
+    if let Some(test_framework) = meson.test_framework {
+        generator = generator.test_framework(test_framework);
+    }
+    if let Some(library_kind) = meson.library_kind {
+        generator = generator.library_kind(library_kind);
+    }
+    Ok(generator.create_project(data)?)
 }
@@ -9,15 +9,16 @@ pub(crate) mod poetry;
 #[cfg(feature = "yarn")]
 pub(crate) mod yarn;
 
-fn retrieve_data<'a, T: serde::Deserialize<'a>>(
+pub(crate) fn retrieve_data<T>(
     config: figment::Figment,
     matches: &clap::ArgMatches,
     toolchain: &str,
-) -> anyhow::Result<T> {
+) -> anyhow::Result<T>
+where
+    T: clap::FromArgMatches + serde::Serialize + serde::de::DeserializeOwned,
+{
     let config = config
-        .merge(crate::ClapSerialized::<crate::CommonData>::globals(
-            matches.clone(),
-        ))
+        .merge(crate::ClapSerialized::<T>::globals(matches.clone()))
         .select(toolchain);
     config.extract::<T>().map_err(|e| e.into())
 }
@@ -2,7 +2,7 @@ use clap::ArgMatches;
 use figment::Figment;
 
 use generate_ci::poetry::Poetry;
-use generate_ci::{CreateProject, TemplateData};
+use generate_ci::CreateProject;
 
 use crate::CommonData;
 
@@ -10,8 +10,6 @@ use super::retrieve_data;
 
 pub(crate) fn poetry_config(config: Figment, matches: &ArgMatches) -> anyhow::Result<()> {
     let poetry = retrieve_data::<CommonData>(config, matches, "poetry")?;
-    let data = TemplateData::new(&poetry.project_path, &poetry.name)
-        .branch(&poetry.branch)
-        .license(&poetry.license);
+    let data = poetry.template_data()?;
     Ok(Poetry::new().create_project(data)?)
 }
@@ -18,9 +18,45 @@ pub enum Error {
     /// Invalid license
     #[error("Invalid license")]
     InvalidLicense(license::ParseError),
+    /// Invalid license expression
+    #[error("Invalid license expression: {0}")]
+    InvalidLicenseExpression(String),
+    /// Invalid custom license identifier
+    #[error("Invalid custom license identifier {0}, expected LicenseRef-<name>")]
+    InvalidLicenseRef(String),
+    /// Custom license without a text
+    #[error("Unknown custom license {0}")]
+    UnknownLicenseRef(String),
+    /// Project or group name not accepted by a toolchain
+    #[error("Invalid name \"{name}\", {reason}: try \"{suggestion}\"")]
+    InvalidName {
+        /// The rejected name.
+        name: String,
+        /// Why the name has been rejected.
+        reason: String,
+        /// A similar valid name.
+        suggestion: String,
+    },
+    /// Toolchain option not supported by the project
+    #[error("Unsupported option: {0}")]
+    UnsupportedOption(String),
+    /// Custom template filter, test or function colliding with a built-in one
+    #[error("The template {kind} {name} collides with a built-in one")]
+    TemplateExtension {
+        /// Whether the extension is a filter, a test or a function.
+        kind: &'static str,
+        /// The colliding name.
+        name: String,
+    },
     /// A more generic I/O error.
     #[error("I/O error")]
     Io(#[from] std::io::Error),
+    /// An error walking the files of a repository.
+    #[error("Unable to list the repository files")]
+    Ignore(#[from] ignore::Error),
+    /// A REUSE.toml parsing error.
+    #[error("REUSE.toml error")]
+    Toml(#[from] toml::de::Error),
     /// A minijinja error.
     #[error("Minijinja error")]
     Minijinja(#[from] minijinja::Error),
@@ -0,0 +1,178 @@
+//! Custom filters, tests and functions of templates.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use minijinja::filters::Filter;
+use minijinja::functions::Function;
+use minijinja::tests::{Test, TestResult};
+use minijinja::value::{FunctionArgs, FunctionResult, Value};
+use minijinja::{Environment, ErrorKind};
+
+use crate::error::{Error, Result};
+
+// Installs an extension into a template environment
+type Install = Arc<dyn Fn(&mut Environment<'static>) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExtensionKind {
+    Filter,
+    Test,
+    Function,
+}
+
+impl ExtensionKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Filter => "filter",
+            Self::Test => "test",
+            Self::Function => "function",
+        }
+    }
+}
+
+/// A filter, test or function added to the built-in ones.
+#[derive(Clone)]
+pub(crate) struct TemplateExtension {
+    kind: ExtensionKind,
+    name: String,
+    install: Install,
+}
+
+impl fmt::Debug for TemplateExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateExtension")
+            .field("kind", &self.kind)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl TemplateExtension {
+    pub(crate) fn filter<F, Rv, Args>(name: &str, f: F) -> Self
+    where
+        F: Filter<Rv, Args> + Clone,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        let filter_name = name.to_owned();
+        Self {
+            kind: ExtensionKind::Filter,
+            name: name.to_owned(),
+            install: Arc::new(move |env| env.add_filter(filter_name.clone(), f.clone())),
+        }
+    }
+
+    pub(crate) fn test<F, Rv, Args>(name: &str, f: F) -> Self
+    where
+        F: Test<Rv, Args> + Clone,
+        Rv: TestResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        let test_name = name.to_owned();
+        Self {
+            kind: ExtensionKind::Test,
+            name: name.to_owned(),
+            install: Arc::new(move |env| env.add_test(test_name.clone(), f.clone())),
+        }
+    }
+
+    pub(crate) fn function<F, Rv, Args>(name: &str, f: F) -> Self
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        let function_name = name.to_owned();
+        let function = Value::from_function(f);
+        Self {
+            kind: ExtensionKind::Function,
+            name: name.to_owned(),
+            install: Arc::new(move |env| env.add_global(function_name.clone(), function.clone())),
+        }
+    }
+
+    // Whether the environment already defines a filter, test or global
+    // with the same name, it is found out by trying to use it
+    fn is_defined(&self, env: &Environment) -> bool {
+        let name = &self.name;
+        let (source, unknown) = match self.kind {
+            ExtensionKind::Filter => (format!("{{{{ none | {name} }}}}"), ErrorKind::UnknownFilter),
+            ExtensionKind::Test => (format!("{{{{ none is {name} }}}}"), ErrorKind::UnknownTest),
+            ExtensionKind::Function => {
+                return env
+                    .render_str(&format!("{{{{ {name} is defined }}}}"), ())
+                    .is_ok_and(|defined| defined == "true")
+            }
+        };
+        match env.render_str(&source, ()) {
+            Ok(_) => true,
+            Err(e) => !matches!(e.kind(), ErrorKind::SyntaxError) && e.kind() != unknown,
+        }
+    }
+
+    // Installs the extension, unless it collides with a built-in filter,
+    // test, function or context variable
+    pub(crate) fn install(
+        &self,
+        env: &mut Environment<'static>,
+        context: &HashMap<&'static str, Value>,
+    ) -> Result<()> {
+        let shadowed =
+            self.kind == ExtensionKind::Function && context.contains_key(self.name.as_str());
+        if shadowed || self.is_defined(env) {
+            return Err(Error::TemplateExtension {
+                kind: self.kind.as_str(),
+                name: self.name.clone(),
+            });
+        }
+        (self.install)(env);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_extensions() {
+        let mut env = Environment::new();
+        let context = HashMap::new();
+
+        TemplateExtension::filter("shout", |value: String| value.to_uppercase())
+            .install(&mut env, &context)
+            .unwrap();
+        TemplateExtension::test("short", |value: String| value.len() < 5)
+            .install(&mut env, &context)
+            .unwrap();
+        TemplateExtension::function("now", || 2024)
+            .install(&mut env, &context)
+            .unwrap();
+
+        let rendered = env
+            .render_str("{{ 'ci' | shout }} {{ 'ci' is short }} {{ now() }}", ())
+            .unwrap();
+        assert_eq!(rendered, "CI true 2024");
+    }
+
+    #[test]
+    fn builtin_collisions() {
+        let mut env = Environment::new();
+        let context = HashMap::from([("name", Value::from("project"))]);
+
+        let collisions = [
+            TemplateExtension::filter("upper", |value: String| value),
+            TemplateExtension::test("defined", |_: Value| true),
+            TemplateExtension::function("range", || 0),
+            TemplateExtension::function("name", || "other"),
+        ];
+        for extension in collisions {
+            assert!(matches!(
+                extension.install(&mut env, &context),
+                Err(Error::TemplateExtension { .. })
+            ));
+        }
+    }
+}
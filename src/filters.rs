@@ -1,27 +1,192 @@
 use minijinja::{Error, State};
 use textwrap::{wrap, Options};
 
+use crate::names::{java_identifier, python_identifier};
+
 const LINE_WIDTH: usize = 79;
 
+/// Case of an identifier derived from a name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Case {
+    /// `snake_case`
+    Snake,
+    /// `kebab-case`
+    Kebab,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+}
+
 pub(crate) fn comment_license(
     _state: &State,
     value: String,
     comment_char: String,
 ) -> Result<String, Error> {
+    Ok(comment_text(&value, &comment_char, LINE_WIDTH))
+}
+
+// Comment out a text, wrapping its lines at the given width
+pub(crate) fn comment_text(value: &str, comment_char: &str, width: usize) -> String {
     let sep = &format!("{comment_char} ");
-    Ok(wrap(
-        &value,
-        Options::new(LINE_WIDTH)
+    wrap(
+        value,
+        Options::new(width)
             .initial_indent(sep)
             .subsequent_indent(sep),
     )
-    .join("\n"))
+    .join("\n")
+}
+
+// Escapes a value placed in a TOML basic string
+pub(crate) fn toml_escape(_state: &State, value: String) -> Result<String, Error> {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    Ok(escaped)
+}
+
+// Escapes a value placed in XML text or attributes
+pub(crate) fn xml_escape(_state: &State, value: String) -> Result<String, Error> {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    Ok(escaped)
 }
 
 pub(crate) fn hypens_to_underscores(_state: &State, value: String) -> Result<String, Error> {
     Ok(value.replace('-', "_"))
 }
 
+// Splits a name into words at separators and case changes,
+// e.g. `HTTPServer-config` into `HTTP`, `Server` and `config`
+fn words(value: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = value.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, &(index, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(start) = start.take() {
+                words.push(&value[start..index]);
+            }
+            continue;
+        }
+        if let Some(word_start) = start {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|&(_, c)| c.is_lowercase());
+            let boundary = c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_lower));
+            if boundary {
+                words.push(&value[word_start..index]);
+                start = Some(index);
+            }
+        } else {
+            start = Some(index);
+        }
+    }
+    if let Some(start) = start {
+        words.push(&value[start..]);
+    }
+    words
+}
+
+// Capitalizes the first letter of a word, lowering the others
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Converts a name to the given case
+pub(crate) fn convert_case(value: &str, case: Case) -> String {
+    let words = words(value);
+    match case {
+        Case::Snake => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::Kebab => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("-"),
+        Case::ScreamingSnake => words
+            .iter()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    w.to_lowercase()
+                } else {
+                    capitalize(w)
+                }
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn snake_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(convert_case(&value, Case::Snake))
+}
+
+pub(crate) fn kebab_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(convert_case(&value, Case::Kebab))
+}
+
+pub(crate) fn pascal_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(convert_case(&value, Case::Pascal))
+}
+
+pub(crate) fn camel_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(convert_case(&value, Case::Camel))
+}
+
+pub(crate) fn screaming_snake_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(convert_case(&value, Case::ScreamingSnake))
+}
+
+pub(crate) fn java_package(_state: &State, value: String) -> Result<String, Error> {
+    Ok(java_identifier(&value))
+}
+
+pub(crate) fn python_module(_state: &State, value: String) -> Result<String, Error> {
+    Ok(python_identifier(&value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,6 +202,59 @@ mod tests {
         .is_ok())
     }
 
+    #[test]
+    fn convert_case_test() {
+        let name = "HTTPServer-config2_v2Api";
+        assert_eq!(
+            convert_case(name, Case::Snake),
+            "http_server_config2_v2_api"
+        );
+        assert_eq!(
+            convert_case(name, Case::Kebab),
+            "http-server-config2-v2-api"
+        );
+        assert_eq!(convert_case(name, Case::Pascal), "HttpServerConfig2V2Api");
+        assert_eq!(convert_case(name, Case::Camel), "httpServerConfig2V2Api");
+        assert_eq!(
+            convert_case(name, Case::ScreamingSnake),
+            "HTTP_SERVER_CONFIG2_V2_API"
+        );
+        assert_eq!(convert_case("my project", Case::Pascal), "MyProject");
+        assert_eq!(convert_case("--", Case::Snake), "");
+    }
+
+    #[test]
+    fn identifiers_test() {
+        let env = Environment::new();
+        let state = env.empty_state();
+        assert_eq!(
+            java_package(&state, "org.My-Group.3d.class".to_owned()).unwrap(),
+            "org.my_group._3d.class_"
+        );
+        assert_eq!(
+            python_module(&state, "Data-Tools".to_owned()).unwrap(),
+            "data_tools"
+        );
+        assert_eq!(
+            python_module(&state, "import".to_owned()).unwrap(),
+            "import_"
+        );
+    }
+
+    #[test]
+    fn escape_test() {
+        let env = Environment::new();
+        let state = env.empty_state();
+        assert_eq!(
+            toml_escape(&state, "A \"quoted\" C:\\path\n\u{7}".to_owned()).unwrap(),
+            "A \\\"quoted\\\" C:\\\\path\\n\\u0007"
+        );
+        assert_eq!(
+            xml_escape(&state, "Tom & Jerry <tj@example.com>".to_owned()).unwrap(),
+            "Tom &amp; Jerry &lt;tj@example.com&gt;"
+        );
+    }
+
     #[test]
     fn hypens_to_underscores_test() {
         assert!(
@@ -0,0 +1,442 @@
+//! SPDX headers of source files.
+
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use ignore::WalkBuilder;
+
+use crate::error::Result;
+use crate::filters::comment_text;
+
+// Tags of the SPDX headers
+pub(crate) const COPYRIGHT_TAG: &str = "SPDX-FileCopyrightText:";
+pub(crate) const LICENSE_TAG: &str = "SPDX-License-Identifier:";
+
+/// Comment syntax used to write a header in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `// comment`
+    DoubleSlash,
+    /// `# comment`
+    Hash,
+    /// `/* comment */`
+    Block,
+    /// `<!-- comment -->`
+    Xml,
+}
+
+impl CommentStyle {
+    /// Guesses the comment style of a file from its extension or name.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        if name.starts_with("Dockerfile") {
+            return Some(Self::Hash);
+        }
+        if matches!(name, ".gitignore" | ".dockerignore") {
+            return Some(Self::Hash);
+        }
+        match path.extension()?.to_str()? {
+            "rs" | "java" | "js" | "jsx" | "ts" | "tsx" | "kt" | "go" => Some(Self::DoubleSlash),
+            "py" | "toml" | "yml" | "yaml" | "sh" | "build" | "cfg" => Some(Self::Hash),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "css" => Some(Self::Block),
+            "xml" | "html" | "md" => Some(Self::Xml),
+            _ => None,
+        }
+    }
+
+    // Write the lines of a comment in this style, SPDX tags must not be
+    // wrapped, so lines are never split
+    fn comment(self, lines: &[String]) -> String {
+        let prefixed = |prefix: &str| -> Vec<String> {
+            lines
+                .iter()
+                .map(|line| {
+                    if line.is_empty() {
+                        prefix.trim_end().to_owned()
+                    } else {
+                        comment_text(line, prefix.trim_end(), usize::MAX)
+                    }
+                })
+                .collect()
+        };
+        let comment = match self {
+            Self::DoubleSlash => prefixed("// "),
+            Self::Hash => prefixed("# "),
+            Self::Block => [
+                vec!["/*".to_owned()],
+                prefixed(" * "),
+                vec![" */".to_owned()],
+            ]
+            .concat(),
+            Self::Xml => [
+                vec!["<!--".to_owned()],
+                lines.to_vec(),
+                vec!["-->".to_owned()],
+            ]
+            .concat(),
+        };
+        comment.join("\n")
+    }
+
+    // Retrieve the lines of the SPDX header at the beginning of the given
+    // lines, together with the number of lines it takes
+    fn parse<'a>(self, lines: &[&'a str]) -> Option<(Vec<&'a str>, usize)> {
+        let is_tag = |line: &str| line.is_empty() || line.starts_with("SPDX-");
+        let (inner, length) = match self {
+            Self::DoubleSlash | Self::Hash => {
+                let prefix = if self == Self::Hash { "#" } else { "//" };
+                let mut inner: Vec<&str> = lines
+                    .iter()
+                    .map_while(|line| line.strip_prefix(prefix).map(str::trim))
+                    .take_while(|line| is_tag(line))
+                    .collect();
+                // Trailing empty comments do not belong to the header
+                while inner.last().is_some_and(|line| line.is_empty()) {
+                    inner.pop();
+                }
+                let length = inner.len();
+                (inner, length)
+            }
+            Self::Block | Self::Xml => {
+                let (open, close) = if self == Self::Block {
+                    ("/*", "*/")
+                } else {
+                    ("<!--", "-->")
+                };
+                if lines.first()?.trim() != open {
+                    return None;
+                }
+                let end = lines.iter().position(|line| line.trim() == close)?;
+                let inner: Vec<&str> = lines[1..end]
+                    .iter()
+                    .map(|line| {
+                        let line = line.trim();
+                        line.strip_prefix('*').map_or(line, str::trim)
+                    })
+                    .collect();
+                if !inner.iter().all(|line| is_tag(line)) {
+                    return None;
+                }
+                (inner, end + 1)
+            }
+        };
+        inner
+            .iter()
+            .any(|line| !line.is_empty())
+            .then_some((inner, length))
+    }
+}
+
+/// State of the SPDX header of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderStatus {
+    /// The header is up to date
+    Current,
+    /// The file has no header
+    Missing,
+    /// The header lacks the copyright holder
+    Outdated,
+    /// The header declares another license, the file is left untouched
+    Conflict,
+    /// The comment syntax of the file is unknown
+    Unsupported,
+}
+
+/// The copyright and licensing information written at the top of a file.
+#[derive(Debug, Clone)]
+pub struct SpdxHeader {
+    copyrights: Vec<String>,
+    license: String,
+}
+
+impl SpdxHeader {
+    /// Creates a new header for the given SPDX license expression.
+    pub fn new(license: &str) -> Self {
+        Self {
+            copyrights: Vec::new(),
+            license: license.to_owned(),
+        }
+    }
+
+    /// Adds a copyright notice, e.g. `2024 Name Surname <email>`.
+    pub fn copyright(mut self, copyright: &str) -> Self {
+        self.copyrights.push(copyright.to_owned());
+        self
+    }
+
+    /// Returns the header written with the given comment style.
+    pub fn render(&self, style: CommentStyle) -> String {
+        let mut lines: Vec<String> = self
+            .copyrights
+            .iter()
+            .map(|copyright| format!("{COPYRIGHT_TAG} {copyright}"))
+            .collect();
+        lines.push(String::new());
+        lines.push(format!("{LICENSE_TAG} {}", self.license));
+        style.comment(&lines)
+    }
+
+    // Prepend the header to the content of a file, after a shebang or an
+    // XML declaration
+    fn prepend(&self, content: &str, style: CommentStyle) -> String {
+        let (preamble, rest) = split_preamble(content);
+        let header = self.render(style);
+        match preamble {
+            Some(preamble) => format!("{preamble}\n{header}\n\n{rest}"),
+            None => format!("{header}\n\n{rest}"),
+        }
+    }
+
+    /// Inserts the header in the content of a file, or updates the existing
+    /// one keeping the copyright notices of other holders.
+    ///
+    /// A file licensed under another expression, e.g. a vendored one, is
+    /// never relicensed and is returned unchanged.
+    pub fn apply(&self, content: &str, style: CommentStyle) -> (String, HeaderStatus) {
+        let (preamble, rest) = split_preamble(content);
+        let lines: Vec<&str> = rest.lines().collect();
+        let Some((inner, length)) = style.parse(&lines) else {
+            return (self.prepend(content, style), HeaderStatus::Missing);
+        };
+        let foreign = inner
+            .iter()
+            .filter_map(|line| line.strip_prefix(LICENSE_TAG))
+            .any(|license| license.trim() != self.license);
+        if foreign {
+            return (content.to_owned(), HeaderStatus::Conflict);
+        }
+
+        // Copyright notices of the same holder differ only in their years
+        let holder = |copyright: &str| {
+            copyright
+                .trim_start_matches(|c: char| c.is_ascii_digit() || ", -".contains(c))
+                .to_owned()
+        };
+        let mut header = Self::new(&self.license);
+        for line in &inner {
+            if let Some(copyright) = line.strip_prefix(COPYRIGHT_TAG) {
+                header = header.copyright(copyright.trim());
+            }
+        }
+        for copyright in &self.copyrights {
+            if !header
+                .copyrights
+                .iter()
+                .any(|existing| holder(existing) == holder(copyright))
+            {
+                header = header.copyright(copyright);
+            }
+        }
+
+        let body = lines[length..].join("\n");
+        let body = body.trim_start_matches('\n');
+        let mut updated = match preamble {
+            Some(preamble) => format!("{preamble}\n{}\n\n{body}", header.render(style)),
+            None => format!("{}\n\n{body}", header.render(style)),
+        };
+        if content.ends_with('\n') && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        let status = if updated == content {
+            HeaderStatus::Current
+        } else {
+            HeaderStatus::Outdated
+        };
+        (updated, status)
+    }
+}
+
+// Split a shebang or an XML declaration from the rest of a file. A Rust
+// inner attribute such as `#![allow(...)]` is not a shebang.
+fn split_preamble(content: &str) -> (Option<&str>, &str) {
+    let is_shebang = |line: &str| line.starts_with("#!/") || line.starts_with("#! ");
+    match content.lines().next() {
+        Some(line) if is_shebang(line) || line.starts_with("<?xml") => (
+            Some(line),
+            content[line.len()..].trim_start_matches(['\r', '\n']),
+        ),
+        _ => (None, content),
+    }
+}
+
+// Whether REUSE ignores a file, e.g. license texts and VCS data
+pub(crate) fn is_reuse_ignored(path: &Path) -> bool {
+    let first = path.components().next().map(|c| c.as_os_str());
+    if first.is_some_and(|first| first == "LICENSES" || first == ".reuse" || first == ".git") {
+        return true;
+    }
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let is_license = |prefix: &str| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '-']))
+    };
+    is_license("LICENSE")
+        || is_license("LICENCE")
+        || is_license("COPYING")
+        || name == "REUSE.toml"
+        || name.ends_with(".license")
+}
+
+// Retrieve the files of a repository relative to its root, skipping those
+// ignored by git. Outside a git work tree, the `.gitignore` files are still
+// honoured.
+pub(crate) fn repository_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .current_dir(root)
+        .output();
+    let mut files = match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .map(PathBuf::from)
+            // Deleted files are still listed until committed
+            .filter(|file| root.join(file).is_file())
+            .collect(),
+        _ => walk(root)?,
+    };
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn walk(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker {
+        let entry = entry?;
+        if entry.file_type().is_some_and(|kind| kind.is_file()) {
+            files.push(entry.path().strip_prefix(root)?.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+/// Inserts or updates the SPDX header of each file in a repository, files
+/// ignored by git or by REUSE are skipped. In check mode, no file is written.
+///
+/// Returns the state each file had before the update, with its path relative
+/// to the repository.
+pub fn update_headers(
+    root: &Path,
+    header: &SpdxHeader,
+    check: bool,
+) -> Result<Vec<(PathBuf, HeaderStatus)>> {
+    let mut statuses = Vec::new();
+    for file in repository_files(root)? {
+        if is_reuse_ignored(&file) {
+            continue;
+        }
+        let path = root.join(&file);
+        // Binary files cannot contain a header
+        let (Some(style), Ok(content)) = (CommentStyle::from_path(&file), read_to_string(&path))
+        else {
+            statuses.push((file, HeaderStatus::Unsupported));
+            continue;
+        };
+        let (updated, status) = header.apply(&content, style);
+        if !check && status != HeaderStatus::Current {
+            write(&path, updated)?;
+        }
+        statuses.push((file, status));
+    }
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comment_styles() {
+        let style = |path: &str| CommentStyle::from_path(Path::new(path));
+        assert_eq!(style("src/main.rs"), Some(CommentStyle::DoubleSlash));
+        assert_eq!(style("lib/meson.build"), Some(CommentStyle::Hash));
+        assert_eq!(style("docker/Dockerfile-amd64"), Some(CommentStyle::Hash));
+        assert_eq!(style("lib/project.h"), Some(CommentStyle::Block));
+        assert_eq!(style("pom.xml"), Some(CommentStyle::Xml));
+        assert_eq!(style("package.json"), None);
+    }
+
+    #[test]
+    fn prepend_header() {
+        let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe");
+        assert_eq!(
+            header.prepend("fn main() {}\n", CommentStyle::DoubleSlash),
+            "// SPDX-FileCopyrightText: 2024 Jane Doe\n//\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            header.prepend("#!/bin/sh\n\necho\n", CommentStyle::Hash),
+            "#!/bin/sh\n# SPDX-FileCopyrightText: 2024 Jane Doe\n#\n# SPDX-License-Identifier: MIT\n\necho\n"
+        );
+        assert_eq!(
+            header.prepend("#![allow(dead_code)]\n", CommentStyle::DoubleSlash),
+            "// SPDX-FileCopyrightText: 2024 Jane Doe\n//\n// SPDX-License-Identifier: MIT\n\n#![allow(dead_code)]\n"
+        );
+        assert_eq!(
+            header.prepend("#! /usr/bin/env python3\n", CommentStyle::Hash),
+            "#! /usr/bin/env python3\n# SPDX-FileCopyrightText: 2024 Jane Doe\n#\n# SPDX-License-Identifier: MIT\n\n"
+        );
+        assert_eq!(
+            header.prepend("int x;", CommentStyle::Block),
+            "/*\n * SPDX-FileCopyrightText: 2024 Jane Doe\n *\n * SPDX-License-Identifier: MIT\n */\n\nint x;"
+        );
+    }
+
+    #[test]
+    fn apply_header() {
+        let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe");
+
+        let (content, status) = header.apply("fn main() {}\n", CommentStyle::DoubleSlash);
+        assert_eq!(status, HeaderStatus::Missing);
+        assert_eq!(
+            header.apply(&content, CommentStyle::DoubleSlash),
+            (content.clone(), HeaderStatus::Current)
+        );
+
+        let (content, status) = SpdxHeader::new("MIT")
+            .copyright("2025 Jane Doe")
+            .copyright("2025 John Doe")
+            .apply(&content, CommentStyle::DoubleSlash);
+        assert_eq!(status, HeaderStatus::Outdated);
+        assert_eq!(
+            content,
+            "// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-FileCopyrightText: 2025 John Doe\n//\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n"
+        );
+
+        // Another license is never rewritten
+        assert_eq!(
+            SpdxHeader::new("Apache-2.0").apply(&content, CommentStyle::DoubleSlash),
+            (content.clone(), HeaderStatus::Conflict)
+        );
+    }
+
+    #[test]
+    fn apply_block_header() {
+        let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe");
+        let (content, _) = header.apply("<?xml version=\"1.0\"?>\n<project/>\n", CommentStyle::Xml);
+        assert_eq!(
+            header.apply(&content, CommentStyle::Xml).1,
+            HeaderStatus::Current
+        );
+        let (content, _) = header.apply("/* A comment */\nint x;\n", CommentStyle::Block);
+        assert!(content.ends_with("*/\n\n/* A comment */\nint x;\n"));
+        assert_eq!(
+            header.apply(&content, CommentStyle::Block).1,
+            HeaderStatus::Current
+        );
+    }
+}
@@ -6,26 +6,198 @@ use error::{Error, Result};
 
 mod command;
 
+mod extensions;
+use extensions::TemplateExtension;
+
 mod filters;
 
-use minijinja::value::Value;
+mod names;
+
+pub mod headers;
+use headers::{CommentStyle, SpdxHeader};
+
+pub mod reuse;
+
+pub mod spdx;
+use spdx::{CustomLicense, LicenseExpression, ProjectLicense};
+
+use minijinja::filters::Filter;
+use minijinja::functions::Function;
+use minijinja::tests::{Test, TestResult};
+use minijinja::value::{FunctionArgs, FunctionResult, Value};
 use minijinja::Environment;
-use std::collections::HashMap;
-use std::fs::{create_dir_all, write};
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, HashMap};
+use std::fs::{create_dir_all, read_to_string, write};
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use filters::*;
 
 static REUSE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/", "dep5"));
 
+static REUSE_TOML_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/",
+    "REUSE.toml"
+));
+
+// Templates never receiving an SPDX header, REUSE ignores them
+const UNHEADED_TEMPLATES: &[&str] = &["dep5.reuse", "toml.reuse", "build.license"];
+
+// Templates of scripts run directly, their files are made executable
+const EXECUTABLE_TEMPLATES: &[&str] = &["sh.mvnw"];
+
+// Context variables added once the toolchain has defined its templates
+const COMPUTED_VARIABLES: &[&str] = &["license", "license_texts", "reuse", "spdx_headers"];
+
+// Placeholders of the year and holders in the copyright lines of license texts
+const YEAR_PLACEHOLDERS: &[&str] = &["<year>", "<YEAR>", "[yyyy]"];
+const HOLDER_PLACEHOLDERS: &[&str] = &[
+    "<copyright holders>",
+    "<COPYRIGHT HOLDER>",
+    "<owner>",
+    "[name of copyright owner]",
+];
+
+// Texts of all licenses referenced by the license expression
+static LICENSE_TEMPLATE: &str =
+    "{% for text in license_texts %}{{ text }}{% if not loop.last %}\n\n{% endif %}{% endfor %}";
+
+/// A project author.
+#[derive(Debug, Serialize)]
+struct Author<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<&'a str>,
+}
+
+impl<'a> Author<'a> {
+    // Splits an author in the `Name Surname <email>` form
+    fn parse(author: &'a str) -> Self {
+        match author.split_once('<') {
+            Some((name, email)) => Self {
+                name: name.trim(),
+                email: Some(email.trim_end_matches('>').trim()),
+            },
+            None => Self {
+                name: author.trim(),
+                email: None,
+            },
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for Author<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.email {
+            Some(email) => write!(f, "{} <{}>", self.name, email),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Project metadata inserted in the context of each template.
+#[derive(Debug, Default, Serialize)]
+struct Metadata<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    authors: Vec<Author<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repository: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    year: Option<u16>,
+}
+
+impl<'a> Metadata<'a> {
+    // Copyright notices of the authors, a placeholder is used when there are none
+    fn copyrights(&self) -> Vec<String> {
+        let year = self.year.map(|year| format!("{year} ")).unwrap_or_default();
+        if self.authors.is_empty() {
+            return vec![format!("{year}Name Surname <mail@example.com>")];
+        }
+        self.authors
+            .iter()
+            .map(|author| format!("{year}{author}"))
+            .collect()
+    }
+
+    // Copyright holders quoted in license texts
+    fn holders(&self) -> Option<String> {
+        (!self.authors.is_empty()).then(|| {
+            self.authors
+                .iter()
+                .map(|author| author.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    // Fills in the year and holders of the copyright lines of a license text,
+    // indented lines belong to usage examples, so they are left untouched
+    fn fill_license(&self, id: &str, text: &str) -> String {
+        let holders = self.holders();
+        let year = self.year.map(|year| year.to_string());
+        let mut unfilled = false;
+        let lines: Vec<String> = text
+            .split('\n')
+            .map(|line| {
+                if !line.starts_with("Copyright")
+                    || !HOLDER_PLACEHOLDERS.iter().any(|p| line.contains(p))
+                {
+                    return line.to_owned();
+                }
+                // A half-filled line would be a wrong copyright notice
+                let has_year = YEAR_PLACEHOLDERS.iter().any(|p| line.contains(p));
+                let Some(holders) = holders.as_ref().filter(|_| year.is_some() || !has_year) else {
+                    unfilled = true;
+                    return line.to_owned();
+                };
+                let line = HOLDER_PLACEHOLDERS
+                    .iter()
+                    .fold(line.to_owned(), |line, p| line.replace(p, holders));
+                match &year {
+                    Some(year) => YEAR_PLACEHOLDERS
+                        .iter()
+                        .fold(line, |line, p| line.replace(p, year)),
+                    None => line,
+                }
+            })
+            .collect();
+        if unfilled {
+            warn!("The {id} license text contains copyright placeholders, but no author or year has been given");
+        }
+        lines.join("\n")
+    }
+}
+
+/// Format of the file describing the licensing of a project to REUSE.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReuseFormat {
+    /// The `.reuse/dep5` file, deprecated by the REUSE specification 3.2
+    Dep5,
+    /// The `REUSE.toml` file
+    #[default]
+    Toml,
+}
+
 #[derive(Debug)]
 pub struct TemplateData<'a> {
     project_path: &'a Path,
     name: &'a str,
     license: &'a str,
     branch: &'a str,
+    custom_licenses: Vec<CustomLicense>,
+    reuse_format: ReuseFormat,
+    spdx_headers: bool,
+    metadata: Metadata<'a>,
+    extensions: Vec<TemplateExtension>,
 }
 impl<'a> TemplateData<'a> {
     /// Creates a new `Common` instance.
@@ -35,6 +207,11 @@ impl<'a> TemplateData<'a> {
             name,
             license: "MIT",
             branch: "main",
+            custom_licenses: Vec::new(),
+            reuse_format: ReuseFormat::default(),
+            spdx_headers: false,
+            metadata: Metadata::default(),
+            extensions: Vec::new(),
         }
     }
     /// Sets a new license.
@@ -43,11 +220,101 @@ impl<'a> TemplateData<'a> {
         self
     }
 
+    /// Adds a custom license which can be referenced by the license expression.
+    pub fn custom_license(mut self, license: CustomLicense) -> Self {
+        self.custom_licenses.push(license);
+        self
+    }
+
+    /// Sets the format of the REUSE licensing information.
+    pub fn reuse_format(mut self, reuse_format: ReuseFormat) -> Self {
+        self.reuse_format = reuse_format;
+        self
+    }
+
+    /// Writes an SPDX header at the top of each generated file supporting
+    /// comments.
+    pub fn spdx_headers(mut self) -> Self {
+        self.spdx_headers = true;
+        self
+    }
+
     /// Sets a new branch.
     pub fn branch(mut self, branch: &'a str) -> Self {
         self.branch = branch;
         self
     }
+
+    /// Sets a project description.
+    pub fn description(mut self, description: &'a str) -> Self {
+        self.metadata.description = Some(description);
+        self
+    }
+
+    /// Adds a project author, in the `Name Surname <email>` form.
+    pub fn author(mut self, author: &'a str) -> Self {
+        self.metadata.authors.push(Author::parse(author));
+        self
+    }
+
+    /// Sets a project homepage.
+    pub fn homepage(mut self, homepage: &'a str) -> Self {
+        self.metadata.homepage = Some(homepage);
+        self
+    }
+
+    /// Sets a project repository URL.
+    pub fn repository(mut self, repository: &'a str) -> Self {
+        self.metadata.repository = Some(repository);
+        self
+    }
+
+    /// Sets a project version.
+    pub fn version(mut self, version: &'a str) -> Self {
+        self.metadata.version = Some(version);
+        self
+    }
+
+    /// Sets the year of the copyright notices, license texts keep their
+    /// copyright placeholders without it.
+    pub fn year(mut self, year: u16) -> Self {
+        self.metadata.year = Some(year);
+        self
+    }
+
+    /// Adds a template filter, its name must not collide with a built-in one.
+    pub fn filter<F, Rv, Args>(mut self, name: &str, f: F) -> Self
+    where
+        F: Filter<Rv, Args> + Clone,
+        Rv: FunctionResult,
+        Args: for<'b> FunctionArgs<'b>,
+    {
+        self.extensions.push(TemplateExtension::filter(name, f));
+        self
+    }
+
+    /// Adds a template test, its name must not collide with a built-in one.
+    pub fn test<F, Rv, Args>(mut self, name: &str, f: F) -> Self
+    where
+        F: Test<Rv, Args> + Clone,
+        Rv: TestResult,
+        Args: for<'b> FunctionArgs<'b>,
+    {
+        self.extensions.push(TemplateExtension::test(name, f));
+        self
+    }
+
+    /// Adds a global template function, its name must not collide with a
+    /// built-in function nor with a context variable.
+    pub fn function<F, Rv, Args>(mut self, name: &str, f: F) -> Self
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'b> FunctionArgs<'b>,
+    {
+        self.extensions.push(TemplateExtension::function(name, f));
+        self
+    }
 }
 
 /// Used to create a CI configuration for a project.
@@ -62,10 +329,30 @@ pub trait CreateProject {
     fn create_project(&self, data: TemplateData) -> Result<()>;
 }
 
+// Windows has no executable permission, scripts are run by extension
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::fs::{metadata, set_permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o111);
+    set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
 struct CiTemplate {
     context: HashMap<&'static str, Value>,
     files: HashMap<PathBuf, &'static str>,
+    file_contexts: FileContexts,
+    texts: HashMap<PathBuf, String>,
     dirs: Vec<PathBuf>,
+    header: Option<SpdxHeader>,
     env: Environment<'static>,
 }
 
@@ -75,8 +362,11 @@ impl CiTemplate {
         let CiTemplate {
             context,
             files,
+            mut file_contexts,
+            texts,
             dirs,
-            mut env,
+            header,
+            env,
         } = self;
 
         // Create dirs
@@ -85,57 +375,168 @@ impl CiTemplate {
             create_dir_all(dir)?;
         }
 
-        env.add_filter("comment_license", comment_license);
-        env.add_filter("hypens_to_underscores", hypens_to_underscores);
-
         // Fill in templates
         for (path, template_name) in files {
             debug!("Creating {}", path.display());
             let template = env.get_template(template_name)?;
-            let filled_template = template.render(&context)?;
-            write(path, filled_template)?;
+            // Some files replace a few variables of the project context
+            let mut filled_template = match file_contexts.remove(&path) {
+                Some(file_context) => {
+                    let mut context = context.clone();
+                    context.extend(file_context);
+                    template.render(&context)?
+                }
+                None => template.render(&context)?,
+            };
+            if let Some(header) = &header {
+                let style = CommentStyle::from_path(&path)
+                    .filter(|_| !UNHEADED_TEMPLATES.contains(&template_name));
+                if let Some(style) = style {
+                    filled_template = header.apply(&filled_template, style).0;
+                }
+            }
+            write(&path, filled_template)?;
+            if EXECUTABLE_TEMPLATES.contains(&template_name) {
+                set_executable(&path)?;
+            }
+        }
+
+        // Write files not containing any template
+        for (path, text) in texts {
+            debug!("Creating {}", path.display());
+            write(path, text)?;
         }
         Ok(())
     }
 
-    fn add_license(&mut self, license: &dyn license::License, project_path: &Path) -> Result<()> {
-        let id = license.id();
-        let header = license.header();
+    // Installs the custom extensions, which cannot replace the built-in
+    // filters, tests, functions and context variables
+    fn install_extensions(&mut self, extensions: &[TemplateExtension]) -> Result<()> {
+        let mut context = self.context.clone();
+        for name in COMPUTED_VARIABLES {
+            context.insert(name, Value::UNDEFINED);
+        }
+        for extension in extensions {
+            extension.install(&mut self.env, &context)?;
+        }
+        Ok(())
+    }
 
-        // Adds LICENSE directory and license file
+    fn add_license(
+        &mut self,
+        license: &LicenseExpression,
+        project_path: &Path,
+        metadata: &Metadata,
+        vendored_files: &[VendoredFiles],
+    ) -> Result<()> {
+        // Adds LICENSE directory and a file for each license and exception,
+        // license texts are not templates, so they are written as they are,
+        // once their copyright placeholders are filled in
         let license_path = project_path.join("LICENSES");
-        self.files
-            .insert(license_path.join(format!("{}.txt", id)), "build.license");
+        let mut license_texts = Vec::new();
+        for license in license.licenses() {
+            let text = metadata.fill_license(license.id(), strip_newline(license.text()));
+            self.texts.insert(
+                license_path.join(format!("{}.txt", license.id())),
+                text.clone(),
+            );
+            license_texts.push(text);
+        }
+        for exception in license.exceptions() {
+            self.texts.insert(
+                license_path.join(format!("{}.txt", exception.id())),
+                strip_newline(exception.text()).to_owned(),
+            );
+        }
+        // Vendored files keep the licenses of their projects
+        for files in vendored_files {
+            let text_path = license_path.join(format!("{}.txt", files.license));
+            if let Entry::Vacant(entry) = self.texts.entry(text_path) {
+                let vendored = LicenseExpression::parse(files.license)?;
+                entry.insert(strip_newline(vendored.licenses()[0].text()).to_owned());
+            }
+        }
         self.dirs.push(license_path);
 
-        let text_without_blank: Vec<&str> = license
-            .text()
-            .lines()
-            .skip(2) // Skip a blank line and license id
-            .filter(|x| !x.is_empty())
+        // The first license is the one quoted in source files, a custom
+        // license is quoted through its header, when present
+        let main_license = &license.licenses()[0];
+        let text_without_blank: Vec<&str> = match main_license {
+            ProjectLicense::Spdx(_) => license_texts[0]
+                .lines()
+                .skip(2) // Skip a blank line and license id
+                .filter(|x| !x.is_empty())
+                .collect(),
+            ProjectLicense::Custom(_) => main_license
+                .header()
+                .unwrap_or(main_license.text())
+                .lines()
+                .filter(|x| !x.is_empty())
+                .collect(),
+        };
+
+        let licenses: Vec<HashMap<&str, Value>> = license
+            .licenses()
+            .iter()
+            .map(|license| {
+                let mut license_ctx = HashMap::new();
+                license_ctx.insert("id", Value::from_serializable(&license.id()));
+                license_ctx.insert("name", Value::from_serializable(&license.name()));
+                license_ctx.insert("url", Value::from_serializable(&license.url()));
+                license_ctx
+            })
             .collect();
 
+        let mut classifiers = Vec::new();
+        for license in license.licenses() {
+            let classifier = license.pypi_classifier();
+            if !classifiers.contains(&classifier) {
+                classifiers.push(classifier);
+            }
+        }
+
         let mut license_ctx = HashMap::new();
 
-        license_ctx.insert("header", Value::from_serializable(&header));
+        license_ctx.insert("header", Value::from_serializable(&main_license.header()));
         license_ctx.insert("text", Value::from_serializable(&text_without_blank));
-        license_ctx.insert("id", Value::from_serializable(&id));
+        license_ctx.insert("id", Value::from_serializable(&license.id()));
+        license_ctx.insert("licenses", Value::from_serializable(&licenses));
+        license_ctx.insert("classifiers", Value::from_serializable(&classifiers));
 
         self.context
             .insert("license", Value::from_serializable(&license_ctx));
+        self.context
+            .insert("license_texts", Value::from_serializable(&license_texts));
 
-        self.env.add_template("build.license", license.text())?;
+        self.env.add_template("build.license", LICENSE_TEMPLATE)?;
 
         Ok(())
     }
 
-    fn add_reuse(&mut self, license: &dyn license::License, project_path: &Path) -> Result<()> {
-        // Adds .reuse directory and dep5 file
-        let reuse_path = project_path.join(".reuse");
-        self.files.insert(reuse_path.join("dep5"), "dep5.reuse");
-        self.dirs.push(reuse_path);
+    fn add_reuse(
+        &mut self,
+        license: &LicenseExpression,
+        project_path: &Path,
+        reuse_format: ReuseFormat,
+        paths: Option<Vec<String>>,
+        vendored_files: &[VendoredFiles],
+    ) -> Result<()> {
+        // Adds either the .reuse directory and dep5 file or REUSE.toml
+        match reuse_format {
+            ReuseFormat::Dep5 => {
+                let reuse_path = project_path.join(".reuse");
+                self.files.insert(reuse_path.join("dep5"), "dep5.reuse");
+                self.dirs.push(reuse_path);
+                self.env.add_template("dep5.reuse", REUSE_TEMPLATE)?;
+            }
+            ReuseFormat::Toml => {
+                self.files
+                    .insert(project_path.join("REUSE.toml"), "toml.reuse");
+                self.env.add_template("toml.reuse", REUSE_TOML_TEMPLATE)?;
+            }
+        }
 
-        // Gets project name and license header
+        // Gets project name and license expression
         let name = self.context.get("name");
         let id = license.id();
 
@@ -143,20 +544,46 @@ impl CiTemplate {
 
         reuse.insert("name", Value::from_serializable(&name));
         reuse.insert("id", Value::from_serializable(&id));
+        // Without SPDX headers, the REUSE file covers the whole project
+        let paths = paths.unwrap_or_else(|| match reuse_format {
+            ReuseFormat::Dep5 => vec!["*".to_owned()],
+            ReuseFormat::Toml => vec!["**".to_owned()],
+        });
+        reuse.insert("paths", Value::from_serializable(&paths));
+        reuse.insert("vendored", Value::from_serializable(&vendored_files));
 
         self.context
             .insert("reuse", Value::from_serializable(&reuse));
 
-        self.env.add_template("dep5.reuse", REUSE_TEMPLATE)?;
-
         Ok(())
     }
 }
 
+// Variables overridden for a single file, by path
+type FileContexts = HashMap<PathBuf, HashMap<&'static str, Value>>;
+
+// Files copied from another project, which keep its copyright and license
+#[derive(Debug, Serialize)]
+struct VendoredFiles {
+    // REUSE.toml patterns, relative to the project path
+    paths: &'static [&'static str],
+    copyright: &'static str,
+    license: &'static str,
+}
+
+impl VendoredFiles {
+    fn contains(&self, path: &str) -> bool {
+        self.paths
+            .iter()
+            .any(|pattern| reuse::glob_match(pattern.as_bytes(), path.as_bytes(), false))
+    }
+}
+
 struct ProjectOutput {
     files: HashMap<PathBuf, &'static str>,
     dirs: Vec<PathBuf>,
     context: HashMap<&'static str, Value>,
+    file_contexts: FileContexts,
 }
 
 /// Build a template
@@ -165,26 +592,57 @@ trait BuildTemplate {
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput>;
 
     fn get_templates() -> &'static [(&'static str, &'static str)];
 
+    // Checks whether the project name is accepted by the toolchain
+    fn validate_name(&self, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    // Checks whether the options of the toolchain are consistent
+    fn validate_options(&self) -> Result<()> {
+        Ok(())
+    }
+
+    // Files created by external tools, relative to the project path
+    fn external_files(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Files copied from other projects
+    fn vendored_files(&self) -> &'static [VendoredFiles] {
+        &[]
+    }
+
+    // Runs the external tools creating the project, once its templates
+    // are known to be valid
+    fn init_project(&self, _project_path: &Path, _metadata: &Metadata) -> Result<()> {
+        Ok(())
+    }
+
     fn build(
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<CiTemplate> {
-        let t = self.define(project_path, project_name, license, github_branch)?;
+        let t = self.define(project_path, project_name, license, github_branch, metadata)?;
         let env = build_environment(Self::get_templates());
 
         Ok(CiTemplate {
             context: t.context,
             files: t.files,
+            file_contexts: t.file_contexts,
+            texts: HashMap::new(),
             dirs: t.dirs,
+            header: None,
             env,
         })
     }
@@ -198,29 +656,116 @@ fn build_environment(templates: &'static [(&'static str, &'static str)]) -> Envi
             .expect("Internal error, built-in template");
     }
 
+    environment.add_filter("comment_license", comment_license);
+    environment.add_filter("hypens_to_underscores", hypens_to_underscores);
+    environment.add_filter("snake_case", snake_case);
+    environment.add_filter("kebab_case", kebab_case);
+    environment.add_filter("pascal_case", pascal_case);
+    environment.add_filter("camel_case", camel_case);
+    environment.add_filter("screaming_snake_case", screaming_snake_case);
+    environment.add_filter("java_package", java_package);
+    environment.add_filter("python_module", python_module);
+    environment.add_filter("toml_escape", toml_escape);
+    environment.add_filter("xml_escape", xml_escape);
+
     environment
 }
 
-// Retrieve the license
-pub(crate) fn define_license(license: &str) -> Result<&dyn license::License> {
-    license
-        .parse::<&dyn license::License>()
-        .map_err(|e| e.into())
+// Retrieve the licenses of a license expression
+pub(crate) fn define_license(
+    license: &str,
+    custom_licenses: &[CustomLicense],
+) -> Result<LicenseExpression> {
+    LicenseExpression::parse_with(license, custom_licenses)
+}
+
+// Remove the trailing newline of a text, as done for templates
+fn strip_newline(text: &str) -> &str {
+    text.strip_suffix('\n')
+        .map(|text| text.strip_suffix('\r').unwrap_or(text))
+        .unwrap_or(text)
 }
 
 // Compute template
 pub(crate) fn compute_template(
     mut template: CiTemplate,
-    license: &dyn license::License,
+    license: &LicenseExpression,
     project_path: &Path,
+    data: &TemplateData,
+    external_files: &[&str],
+    vendored_files: &[VendoredFiles],
 ) -> Result<()> {
-    template.add_reuse(license, project_path)?;
-    template.add_license(license, project_path)?;
+    let paths = if data.spdx_headers {
+        let header = data
+            .metadata
+            .copyrights()
+            .iter()
+            .fold(SpdxHeader::new(license.id()), |header, copyright| {
+                header.copyright(copyright)
+            });
+
+        // Files without comments are left to the REUSE file
+        let mut paths = Vec::new();
+        for file in external_files {
+            let path = project_path.join(file);
+            if !path.is_file() {
+                continue;
+            }
+            match CommentStyle::from_path(&path) {
+                Some(style) => {
+                    let content = read_to_string(&path)?;
+                    // Files already carrying a header, e.g. in a project generated
+                    // again, are updated instead of getting another one
+                    write(&path, header.apply(&content, style).0)?;
+                }
+                None => paths.push(file.to_string()),
+            }
+        }
+        for path in template.files.keys() {
+            if CommentStyle::from_path(path).is_none() {
+                let path = path.strip_prefix(project_path).unwrap_or(path);
+                let path = path.to_string_lossy().replace('\\', "/");
+                // Vendored files are described by their own annotation
+                if !vendored_files.iter().any(|files| files.contains(&path)) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+
+        template.header = Some(header);
+        Some(paths)
+    } else {
+        None
+    };
+    template
+        .context
+        .insert("spdx_headers", Value::from(data.spdx_headers));
+
+    template.add_reuse(
+        license,
+        project_path,
+        data.reuse_format,
+        paths,
+        vendored_files,
+    )?;
+    template.add_license(license, project_path, &data.metadata, vendored_files)?;
     template.render()
 }
 
 // Performs path validation
 pub fn path_validation(project_path: &Path) -> Result<PathBuf> {
+    let project_path = resolve_path(project_path)?;
+
+    // Create directories recursively when they do not exist
+    create_dir_all(&project_path)?;
+
+    Ok(project_path)
+}
+
+// Validates a project path and expands the home directory, without touching
+// the filesystem
+pub(crate) fn resolve_path(project_path: &Path) -> Result<PathBuf> {
     // Do not accept a file, only a directory
     if project_path.is_file() {
         return Err(Error::NoDirectory);
@@ -248,9 +793,6 @@ pub fn path_validation(project_path: &Path) -> Result<PathBuf> {
         project_path.to_path_buf()
     };
 
-    // Create directories recursively when they do not exist
-    create_dir_all(&project_path)?;
-
     Ok(project_path)
 }
 
@@ -272,7 +814,7 @@ mod tests {
         #[test]
         fn define_license_proptest(data: LicenseTest) {
 
-            match define_license(&data.license_str) {
+            match define_license(&data.license_str, &[]) {
                 Ok(_) => prop_assert!(VALID_LICENSES.contains(&data.license_str.as_str())),
                 Err(Error::InvalidLicense(_)) => prop_assert!(!VALID_LICENSES.contains(&data.license_str.as_str())),
                 // All other use-cases are not considered
@@ -281,6 +823,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fill_license() {
+        let metadata = Metadata {
+            authors: vec![
+                Author::parse("Jane Doe <jane@doe.org>"),
+                Author::parse("John Doe"),
+            ],
+            year: Some(2024),
+            ..Default::default()
+        };
+        let mit = "MIT".parse::<&dyn license::License>().unwrap();
+        let text = metadata.fill_license("MIT", mit.text());
+        assert!(text.contains("Copyright (c) 2024 Jane Doe, John Doe\n"));
+        assert!(!text.contains("<year>"));
+
+        // Usage examples are left untouched
+        let gpl = "GPL-3.0-only".parse::<&dyn license::License>().unwrap();
+        let text = metadata.fill_license("GPL-3.0-only", gpl.text());
+        assert!(text.contains("Copyright (C) <year>  <name of author>"));
+
+        // Placeholders are kept without any holder
+        let text = Metadata::default().fill_license("MIT", mit.text());
+        assert!(text.contains("Copyright (c) <year> <copyright holders>"));
+
+        // Placeholders are kept without any year
+        let metadata = Metadata {
+            year: None,
+            ..metadata
+        };
+        let text = metadata.fill_license("MIT", mit.text());
+        assert!(text.contains("Copyright (c) <year> <copyright holders>"));
+    }
+
     #[test]
     fn test_invalid_path_file() {
         let repo_path = env::var("CARGO_MANIFEST_DIR")
@@ -324,4 +899,33 @@ mod tests {
             Err(Error::Utf8Check)
         ));
     }
+
+    #[test]
+    fn test_render_extensions() {
+        let path = env::temp_dir().join("extensions_render");
+        let data = TemplateData::new(&path, "ci")
+            .filter("shout", |value: String| value.to_uppercase())
+            .test("short", |value: String| value.len() < 5)
+            .function("git_remote", || "https://github.com/example/ci");
+
+        let mut template = CiTemplate {
+            context: HashMap::from([("name", Value::from(data.name))]),
+            files: HashMap::from([(path.join("README.md"), "readme")]),
+            file_contexts: HashMap::new(),
+            texts: HashMap::new(),
+            dirs: vec![path.clone()],
+            header: None,
+            env: build_environment(&[(
+                "readme",
+                "{{ name | shout }}{% if name is short %} is short{% endif %}, {{ git_remote() }}",
+            )]),
+        };
+        template.install_extensions(&data.extensions).unwrap();
+        template.render().unwrap();
+
+        assert_eq!(
+            read_to_string(path.join("README.md")).unwrap(),
+            "CI is short, https://github.com/example/ci"
+        );
+    }
 }
@@ -0,0 +1,466 @@
+//! Project name rules of the supported toolchains.
+
+use crate::error::{Error, Result};
+use crate::filters::{convert_case, Case};
+
+// Longest crate name accepted by crates.io
+const CRATE_NAME_MAX_LEN: usize = 64;
+
+// Rust keywords and crates of the standard library
+const RUST_RESERVED: &[&str] = &[
+    "abstract",
+    "alloc",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "core",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "proc-macro",
+    "proc_macro",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "std",
+    "struct",
+    "super",
+    "test",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
+
+// Python keywords, lowercase module names can only clash with these
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+// Java keywords and literals
+const JAVA_KEYWORDS: &[&str] = &[
+    "_",
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+];
+
+// C and C++ keywords, a Meson project can use either language
+const C_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "asm",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "constexpr",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "explicit",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "nullptr",
+    "operator",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+fn invalid(name: &str, reason: &str) -> Error {
+    Error::InvalidName {
+        name: name.to_owned(),
+        reason: reason.to_owned(),
+        suggestion: suggest(name),
+    }
+}
+
+// A name accepted by all toolchains: lowercase ASCII words separated by
+// hyphens, never starting with a digit nor being a keyword
+fn suggest(name: &str) -> String {
+    let mut suggestion = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            suggestion.push(c.to_ascii_lowercase());
+        } else if !suggestion.is_empty() && !suggestion.ends_with('-') {
+            suggestion.push('-');
+        }
+    }
+    let suggestion = suggestion.trim_end_matches('-');
+
+    let reserved = |word: &str| {
+        [RUST_RESERVED, PYTHON_KEYWORDS, JAVA_KEYWORDS, C_KEYWORDS]
+            .iter()
+            .any(|keywords| keywords.contains(&word))
+    };
+    let mut suggestion = if suggestion.is_empty() {
+        "project".to_owned()
+    } else if suggestion.starts_with(|c: char| c.is_ascii_digit()) || reserved(suggestion) {
+        format!("project-{suggestion}")
+    } else {
+        suggestion.to_owned()
+    };
+    suggestion.truncate(CRATE_NAME_MAX_LEN);
+    suggestion.trim_end_matches('-').to_owned()
+}
+
+// Whether a name only contains ASCII alphanumeric characters and the given
+// separators, without starting with a digit
+fn is_identifier_like(name: &str, separators: &[char]) -> bool {
+    !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || separators.contains(&c))
+}
+
+// A lowercase identifier, prefixed by an underscore when it starts with a
+// digit and suffixed by an underscore when it is a keyword
+fn identifier(value: &str, keywords: &[&str]) -> String {
+    let mut identifier = convert_case(value, Case::Snake);
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if keywords.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Python module derived from a name, also given by the `python_module` filter.
+pub(crate) fn python_identifier(value: &str) -> String {
+    identifier(value, PYTHON_KEYWORDS)
+}
+
+/// Java package derived from a name, each dot-separated component becoming
+/// an identifier, also given by the `java_package` filter.
+pub(crate) fn java_identifier(value: &str) -> String {
+    value
+        .split('.')
+        .filter(|component| !component.is_empty())
+        .map(|component| identifier(component, JAVA_KEYWORDS))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Validates a crate name, returning the name used in Rust code.
+pub(crate) fn crate_name(name: &str) -> Result<String> {
+    if name.is_empty() {
+        return Err(invalid(name, "a crate name cannot be empty"));
+    }
+    if name.len() > CRATE_NAME_MAX_LEN {
+        return Err(invalid(
+            name,
+            "a crate name cannot be longer than 64 characters",
+        ));
+    }
+    if !is_identifier_like(name, &['-', '_']) {
+        return Err(invalid(
+            name,
+            "a crate name must start with a letter and only contain ASCII alphanumeric characters, `-` or `_`",
+        ));
+    }
+    if RUST_RESERVED.contains(&name) {
+        return Err(invalid(name, "a crate name cannot be a Rust keyword"));
+    }
+    Ok(name.replace('-', "_"))
+}
+
+/// Validates a Python distribution name, returning the name of its module.
+pub(crate) fn python_module(name: &str) -> Result<String> {
+    // PEP 508 names start and end with an alphanumeric character
+    let alphanumeric_ends = name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric());
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !alphanumeric_ends || !valid_chars {
+        return Err(invalid(
+            name,
+            "a Python package name must only contain ASCII alphanumeric characters, `-`, `_` or `.`, and start and end with an alphanumeric character",
+        ));
+    }
+
+    // The derived identifier is only escaped when the name is not valid
+    let module = python_identifier(name);
+    if module.starts_with('_') {
+        return Err(invalid(name, "a Python module cannot start with a digit"));
+    }
+    if module.ends_with('_') {
+        return Err(invalid(name, "a Python module cannot be a keyword"));
+    }
+    Ok(module)
+}
+
+/// Validates a Maven artifactId, returning the Java package nested in the
+/// one of the group.
+pub(crate) fn java_package(name: &str) -> Result<String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(invalid(
+            name,
+            "an artifactId must only contain ASCII alphanumeric characters, `-`, `_` or `.`",
+        ));
+    }
+
+    let package = java_identifier(name);
+    let components: Vec<&str> = package.split('.').collect();
+    if components.iter().any(|component| component.is_empty()) {
+        return Err(invalid(
+            name,
+            "each component of an artifactId must contain an ASCII alphanumeric character",
+        ));
+    }
+    if components
+        .iter()
+        .any(|component| component.starts_with('_'))
+    {
+        return Err(invalid(name, "a Java package cannot start with a digit"));
+    }
+    if components.iter().any(|component| component.ends_with('_')) {
+        return Err(invalid(name, "a Java package cannot be a keyword"));
+    }
+    Ok(package)
+}
+
+/// Validates a Maven groupId, each dot-separated component of its Java
+/// package must be an identifier.
+pub(crate) fn java_group(group: &str) -> Result<()> {
+    let valid = group.split('.').all(|component| {
+        !component.is_empty()
+            && is_identifier_like(component, &['_', '$'])
+            && !JAVA_KEYWORDS.contains(&component)
+    });
+    if !valid {
+        return Err(Error::InvalidName {
+            name: group.to_owned(),
+            reason: "each component of a Maven group must be a Java identifier".to_owned(),
+            suggestion: java_identifier(group),
+        });
+    }
+    Ok(())
+}
+
+/// Validates a Meson project name, returning the name used in C and C++
+/// identifiers.
+pub(crate) fn c_identifier(name: &str) -> Result<String> {
+    if name.is_empty() || !is_identifier_like(name, &['-', '_']) {
+        return Err(invalid(
+            name,
+            "a Meson project name must start with a letter and only contain ASCII alphanumeric characters, `-` or `_`",
+        ));
+    }
+
+    let identifier = identifier(name, C_KEYWORDS);
+    if identifier.ends_with('_') {
+        return Err(invalid(
+            name,
+            "a Meson project name cannot be a C or C++ keyword",
+        ));
+    }
+    Ok(identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion<T>(result: Result<T>) -> String {
+        match result {
+            Err(Error::InvalidName { suggestion, .. }) => suggestion,
+            _ => panic!("Expected an invalid name"),
+        }
+    }
+
+    #[test]
+    fn crate_names() {
+        assert_eq!(crate_name("my-crate").unwrap(), "my_crate");
+        assert_eq!(crate_name("My_Crate2").unwrap(), "My_Crate2");
+        assert_eq!(suggestion(crate_name("my crate")), "my-crate");
+        assert_eq!(suggestion(crate_name("1password")), "project-1password");
+        assert_eq!(suggestion(crate_name("test")), "project-test");
+        assert_eq!(suggestion(crate_name("")), "project");
+        assert!(crate_name(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn python_modules() {
+        assert_eq!(python_module("poetry-python").unwrap(), "poetry_python");
+        assert_eq!(python_module("Data.Tools").unwrap(), "data_tools");
+        assert_eq!(python_module("DataTools").unwrap(), "data_tools");
+        assert_eq!(suggestion(python_module("-tools")), "tools");
+        assert_eq!(suggestion(python_module("3d-tools")), "project-3d-tools");
+        assert_eq!(suggestion(python_module("import")), "project-import");
+    }
+
+    #[test]
+    fn java_packages() {
+        assert_eq!(java_package("maven-java").unwrap(), "maven_java");
+        // The same package as the `java_package` filter
+        assert_eq!(java_package("MyApp").unwrap(), "my_app");
+        assert_eq!(java_package("my.App").unwrap(), "my.app");
+        assert_eq!(suggestion(java_package("my.3d")), "my-3d");
+        assert_eq!(suggestion(java_package("my@app")), "my-app");
+        assert_eq!(suggestion(java_package("package")), "project-package");
+    }
+
+    #[test]
+    fn java_groups() {
+        assert!(java_group("com.example").is_ok());
+        assert!(java_group("POL").is_ok());
+        assert_eq!(suggestion(java_group("com.my-org")), "com.my_org");
+        assert_eq!(suggestion(java_group("org..2fa")), "org._2fa");
+        assert_eq!(suggestion(java_group("org.new")), "org.new_");
+    }
+
+    #[test]
+    fn c_identifiers() {
+        assert_eq!(c_identifier("meson-c").unwrap(), "meson_c");
+        assert_eq!(c_identifier("MesonC").unwrap(), "meson_c");
+        assert_eq!(suggestion(c_identifier("meson.c")), "meson-c");
+        assert_eq!(suggestion(c_identifier("class")), "project-class");
+    }
+
+    #[test]
+    fn suggestions_are_valid() {
+        for name in ["my crate", "1password", "test", "", "-x-", "class", "über"] {
+            let suggestion = suggest(name);
+            assert!(crate_name(&suggestion).is_ok(), "{suggestion}");
+            assert!(python_module(&suggestion).is_ok(), "{suggestion}");
+            assert!(java_package(&suggestion).is_ok(), "{suggestion}");
+            assert!(c_identifier(&suggestion).is_ok(), "{suggestion}");
+        }
+    }
+}
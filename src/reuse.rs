@@ -0,0 +1,571 @@
+//! REUSE compliance checks.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{read, read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::Result;
+use crate::headers::{is_reuse_ignored, repository_files, COPYRIGHT_TAG, LICENSE_TAG};
+
+// Version of the REUSE specification the checks refer to
+const REUSE_VERSION: &str = "3.3";
+
+// Operators of an SPDX license expression
+const OPERATORS: &[&str] = &["AND", "OR", "WITH"];
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for OneOrMany {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(value) => vec![value],
+            Self::Many(values) => values,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ReuseToml {
+    #[serde(default)]
+    annotations: Vec<TomlAnnotation>,
+}
+
+// How the information of an annotation is combined with the one of a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Precedence {
+    // The information in the file is used, the annotation fills the gaps
+    #[default]
+    Closest,
+    // The information in the file and in the annotation is combined
+    Aggregate,
+    // Only the information in the annotation is used
+    Override,
+}
+
+#[derive(Deserialize)]
+struct TomlAnnotation {
+    path: OneOrMany,
+    #[serde(default)]
+    precedence: Precedence,
+    #[serde(rename = "SPDX-FileCopyrightText", default)]
+    copyrights: OneOrMany,
+    #[serde(rename = "SPDX-License-Identifier", default)]
+    licenses: OneOrMany,
+}
+
+// Licensing information given to the files matching some patterns, either
+// by REUSE.toml or by .reuse/dep5
+struct Annotation {
+    patterns: Vec<String>,
+    // In dep5 patterns, `*` matches `/` as well
+    dep5: bool,
+    precedence: Precedence,
+    copyright: bool,
+    licenses: Vec<String>,
+}
+
+impl Annotation {
+    fn matches(&self, path: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| glob_match(pattern.as_bytes(), path.as_bytes(), self.dep5))
+    }
+}
+
+// Match a path against a REUSE.toml or a dep5 glob pattern
+pub(crate) fn glob_match(pattern: &[u8], path: &[u8], dep5: bool) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(b'\\') if !dep5 && pattern.len() > 1 => {
+            path.first() == Some(&pattern[1]) && glob_match(&pattern[2..], &path[1..], dep5)
+        }
+        Some(b'*') => {
+            let (rest, any) = if !dep5 && pattern.get(1) == Some(&b'*') {
+                (&pattern[2..], true)
+            } else {
+                (&pattern[1..], dep5)
+            };
+            (0..=path.len())
+                .take_while(|&i| any || i == 0 || path[i - 1] != b'/')
+                .any(|i| glob_match(rest, &path[i..], dep5))
+        }
+        Some(b'?') if dep5 => !path.is_empty() && glob_match(&pattern[1..], &path[1..], dep5),
+        Some(c) => path.first() == Some(c) && glob_match(&pattern[1..], &path[1..], dep5),
+    }
+}
+
+fn toml_annotations(path: &Path) -> Result<Vec<Annotation>> {
+    let reuse: ReuseToml = toml::from_str(&read_to_string(path)?)?;
+    Ok(reuse
+        .annotations
+        .into_iter()
+        .map(|annotation| Annotation {
+            patterns: annotation.path.into_vec(),
+            dep5: false,
+            precedence: annotation.precedence,
+            copyright: !annotation.copyrights.into_vec().is_empty(),
+            licenses: annotation.licenses.into_vec(),
+        })
+        .collect())
+}
+
+fn dep5_annotations(path: &Path) -> Result<Vec<Annotation>> {
+    let content = read_to_string(path)?;
+    let mut annotations = Vec::new();
+    for paragraph in content.split("\n\n") {
+        // Join the continuation lines to their fields
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in paragraph.lines() {
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                fields.push((name.trim().to_owned(), value.trim().to_owned()));
+            }
+        }
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let Some(files) = field("Files") else {
+            continue;
+        };
+        annotations.push(Annotation {
+            patterns: files
+                .split_whitespace()
+                .map(|pattern| pattern.trim_start_matches("./").to_owned())
+                .collect(),
+            dep5: true,
+            // The information of dep5 is always combined with the one of files
+            precedence: Precedence::Aggregate,
+            copyright: field("Copyright").is_some_and(|value| !value.is_empty()),
+            licenses: field("License")
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned)
+                .into_iter()
+                .collect(),
+        });
+    }
+    Ok(annotations)
+}
+
+// Licensing information contained in a file
+#[derive(Default)]
+struct FileInfo {
+    copyright: bool,
+    licenses: Vec<String>,
+}
+
+fn scan(content: &str) -> FileInfo {
+    let mut info = FileInfo::default();
+    let mut ignored = false;
+    for line in content.lines() {
+        if line.contains("REUSE-IgnoreStart") {
+            ignored = true;
+        } else if line.contains("REUSE-IgnoreEnd") {
+            ignored = false;
+        }
+        if ignored {
+            continue;
+        }
+        if let Some((_, value)) = line.split_once(LICENSE_TAG) {
+            // Drop comment terminators and anything not in an expression
+            let value = value.replace("*/", " ").replace("-->", " ");
+            let expression: String = value
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || " .-+:()".contains(*c))
+                .collect();
+            if !expression.trim().is_empty() {
+                info.licenses.push(expression.trim().to_owned());
+            }
+        }
+        if line.contains(COPYRIGHT_TAG) || is_copyright_notice(line) {
+            info.copyright = true;
+        }
+    }
+    info
+}
+
+// Whether a line contains a copyright notice not using the SPDX tag
+fn is_copyright_notice(line: &str) -> bool {
+    let line = line
+        .trim()
+        .trim_start_matches(['/', '#', '*', '!', '-', '<', ';', '%'])
+        .trim();
+    line.starts_with("SPDX-SnippetCopyrightText:")
+        || line.starts_with('©')
+        || line.starts_with("Copyright (c)")
+        || line.starts_with("Copyright (C)")
+        || line.starts_with("Copyright ©")
+        || line
+            .strip_prefix("Copyright ")
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+// Identifiers of the licenses and exceptions of an expression
+fn license_ids(expression: &str) -> impl Iterator<Item = &str> {
+    expression
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|token| !token.is_empty())
+        .filter(|token| !OPERATORS.contains(&token.to_uppercase().as_str()))
+        // The `+` suffix does not change the license text
+        .map(|token| token.strip_suffix('+').unwrap_or(token))
+}
+
+// Whether a license identifier is valid, and whether it is deprecated
+fn check_id(id: &str) -> Option<bool> {
+    if let Some(name) = id.strip_prefix("LicenseRef-") {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        return valid.then_some(false);
+    }
+    if let Ok(license) = id.parse::<&dyn license::License>() {
+        return Some(license.is_deprecated());
+    }
+    id.parse::<&dyn license::Exception>()
+        .ok()
+        .map(|exception| exception.is_deprecated())
+}
+
+/// The result of the REUSE compliance checks of a project.
+#[derive(Debug, Default)]
+pub struct LintReport {
+    bad_licenses: BTreeMap<String, Vec<String>>,
+    deprecated_licenses: BTreeSet<String>,
+    missing_licenses: BTreeMap<String, Vec<String>>,
+    unused_licenses: Vec<String>,
+    used_licenses: BTreeSet<String>,
+    files_without_copyright: Vec<String>,
+    files_without_license: Vec<String>,
+    files: usize,
+    dual_configuration: bool,
+}
+
+impl LintReport {
+    /// Returns whether the project complies with the REUSE specification.
+    pub fn is_compliant(&self) -> bool {
+        !self.dual_configuration
+            && self.bad_licenses.is_empty()
+            && self.deprecated_licenses.is_empty()
+            && self.missing_licenses.is_empty()
+            && self.unused_licenses.is_empty()
+            && self.files_without_copyright.is_empty()
+            && self.files_without_license.is_empty()
+    }
+
+    /// Returns the invalid license identifiers.
+    pub fn bad_licenses(&self) -> impl Iterator<Item = &str> {
+        self.bad_licenses.keys().map(String::as_str)
+    }
+
+    /// Returns the deprecated license identifiers.
+    pub fn deprecated_licenses(&self) -> impl Iterator<Item = &str> {
+        self.deprecated_licenses.iter().map(String::as_str)
+    }
+
+    /// Returns the licenses without a text in the `LICENSES` directory.
+    pub fn missing_licenses(&self) -> impl Iterator<Item = &str> {
+        self.missing_licenses.keys().map(String::as_str)
+    }
+
+    /// Returns the license texts not referenced by any file.
+    pub fn unused_licenses(&self) -> &[String] {
+        &self.unused_licenses
+    }
+
+    /// Returns the files without copyright information.
+    pub fn files_without_copyright(&self) -> &[String] {
+        &self.files_without_copyright
+    }
+
+    /// Returns the files without licensing information.
+    pub fn files_without_license(&self) -> &[String] {
+        &self.files_without_license
+    }
+
+    /// Returns whether the project has both `.reuse/dep5` and `REUSE.toml`,
+    /// which the specification forbids.
+    pub fn dual_configuration(&self) -> bool {
+        self.dual_configuration
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found_in = |f: &mut fmt::Formatter<'_>, licenses: &BTreeMap<String, Vec<String>>| {
+            for (id, files) in licenses {
+                writeln!(f, "'{id}' found in:")?;
+                for file in files {
+                    writeln!(f, "* {file}")?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        };
+
+        if self.dual_configuration {
+            writeln!(f, "# CONFLICTING CONFIGURATION\n")?;
+            writeln!(
+                f,
+                "Both .reuse/dep5 and REUSE.toml exist, only one of them can be used.\n"
+            )?;
+        }
+        if !self.bad_licenses.is_empty() {
+            writeln!(f, "# BAD LICENSES\n")?;
+            found_in(f, &self.bad_licenses)?;
+        }
+        if !self.deprecated_licenses.is_empty() {
+            writeln!(f, "# DEPRECATED LICENSES\n")?;
+            writeln!(f, "The following licenses are deprecated by SPDX:")?;
+            for id in &self.deprecated_licenses {
+                writeln!(f, "* {id}")?;
+            }
+            writeln!(f)?;
+        }
+        if !self.missing_licenses.is_empty() {
+            writeln!(f, "# MISSING LICENSES\n")?;
+            found_in(f, &self.missing_licenses)?;
+        }
+        if !self.unused_licenses.is_empty() {
+            writeln!(f, "# UNUSED LICENSES\n")?;
+            writeln!(f, "The following licenses are not used:")?;
+            for id in &self.unused_licenses {
+                writeln!(f, "* {id}")?;
+            }
+            writeln!(f)?;
+        }
+        if !self.files_without_copyright.is_empty() || !self.files_without_license.is_empty() {
+            writeln!(f, "# MISSING COPYRIGHT AND LICENSING INFORMATION\n")?;
+            if !self.files_without_copyright.is_empty() {
+                writeln!(f, "The following files have no copyright information:")?;
+                for file in &self.files_without_copyright {
+                    writeln!(f, "* {file}")?;
+                }
+                writeln!(f)?;
+            }
+            if !self.files_without_license.is_empty() {
+                writeln!(f, "The following files have no licensing information:")?;
+                for file in &self.files_without_license {
+                    writeln!(f, "* {file}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        let used: Vec<&str> = self.used_licenses.iter().map(String::as_str).collect();
+        writeln!(f, "# SUMMARY\n")?;
+        writeln!(f, "* Bad licenses: {}", self.bad_licenses.len())?;
+        writeln!(
+            f,
+            "* Deprecated licenses: {}",
+            self.deprecated_licenses.len()
+        )?;
+        writeln!(f, "* Missing licenses: {}", self.missing_licenses.len())?;
+        writeln!(f, "* Unused licenses: {}", self.unused_licenses.len())?;
+        writeln!(f, "* Used licenses: {}", used.join(", "))?;
+        writeln!(
+            f,
+            "* Files with copyright information: {} / {}",
+            self.files - self.files_without_copyright.len(),
+            self.files
+        )?;
+        writeln!(
+            f,
+            "* Files with license information: {} / {}\n",
+            self.files - self.files_without_license.len(),
+            self.files
+        )?;
+
+        if self.is_compliant() {
+            write!(
+                f,
+                "Congratulations! Your project is compliant with version {REUSE_VERSION} of the REUSE Specification :-)"
+            )
+        } else {
+            write!(
+                f,
+                "Unfortunately, your project is not compliant with version {REUSE_VERSION} of the REUSE Specification :-("
+            )
+        }
+    }
+}
+
+/// Checks whether a project complies with the REUSE specification.
+///
+/// Each file, except those ignored by git, must have copyright and licensing
+/// information, given by an SPDX header, a `.license` file, `REUSE.toml` or
+/// `.reuse/dep5`. Each referenced license must have a text in the `LICENSES`
+/// directory, and each text must be referenced.
+///
+/// The `precedence` of the `REUSE.toml` annotations is honoured, while the
+/// information of `.reuse/dep5` is combined with the one of each file. A
+/// project cannot have both files.
+pub fn lint(path: &Path) -> Result<LintReport> {
+    let mut report = LintReport::default();
+    let toml_path = path.join("REUSE.toml");
+    let dep5_path = path.join(".reuse/dep5");
+    report.dual_configuration = toml_path.is_file() && dep5_path.is_file();
+
+    let mut annotations = Vec::new();
+    if toml_path.is_file() {
+        annotations.extend(toml_annotations(&toml_path)?);
+    }
+    if dep5_path.is_file() {
+        annotations.extend(dep5_annotations(&dep5_path)?);
+    }
+
+    let mut used: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in repository_files(path)? {
+        if is_reuse_ignored(&file) {
+            continue;
+        }
+        let name = file.to_string_lossy().replace('\\', "/");
+
+        // A `.license` file replaces the header of the file it describes
+        let mut sidecar = file.clone().into_os_string();
+        sidecar.push(".license");
+        let sidecar = path.join(PathBuf::from(sidecar));
+        let content = if sidecar.is_file() {
+            read(sidecar)?
+        } else {
+            read(path.join(&file))?
+        };
+        let mut info = scan(&String::from_utf8_lossy(&content));
+        // As in REUSE.toml and dep5, the last matching annotation applies
+        if let Some(annotation) = annotations.iter().rev().find(|a| a.matches(&name)) {
+            match annotation.precedence {
+                Precedence::Closest => {
+                    info.copyright |= annotation.copyright;
+                    if info.licenses.is_empty() {
+                        info.licenses = annotation.licenses.clone();
+                    }
+                }
+                Precedence::Aggregate => {
+                    info.copyright |= annotation.copyright;
+                    info.licenses.extend(annotation.licenses.iter().cloned());
+                }
+                Precedence::Override => {
+                    info.copyright = annotation.copyright;
+                    info.licenses = annotation.licenses.clone();
+                }
+            }
+        }
+
+        report.files += 1;
+        if !info.copyright {
+            report.files_without_copyright.push(name.clone());
+        }
+        if info.licenses.is_empty() {
+            report.files_without_license.push(name.clone());
+        }
+        for expression in &info.licenses {
+            for id in license_ids(expression) {
+                let files = used.entry(id.to_owned()).or_default();
+                if !files.contains(&name) {
+                    files.push(name.clone());
+                }
+            }
+        }
+    }
+
+    // License texts are named after their identifiers
+    let mut texts = BTreeSet::new();
+    if path.join("LICENSES").is_dir() {
+        for entry in read_dir(path.join("LICENSES"))? {
+            let entry = PathBuf::from(entry?.file_name());
+            if let Some(id) = entry.file_stem().and_then(|stem| stem.to_str()) {
+                texts.insert(id.to_owned());
+            }
+        }
+    }
+
+    for (id, files) in used {
+        match check_id(&id) {
+            None => {
+                report.bad_licenses.insert(id.clone(), files.clone());
+            }
+            Some(true) => {
+                report.deprecated_licenses.insert(id.clone());
+            }
+            Some(false) => {}
+        }
+        if !texts.contains(&id) {
+            report.missing_licenses.insert(id.clone(), files);
+        }
+        report.used_licenses.insert(id);
+    }
+    report.unused_licenses = texts
+        .into_iter()
+        .filter(|id| !report.used_licenses.contains(id))
+        .collect();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_patterns() {
+        let toml =
+            |pattern: &str, path: &str| glob_match(pattern.as_bytes(), path.as_bytes(), false);
+        assert!(toml("**", "src/main.rs"));
+        assert!(toml("*.rs", "main.rs"));
+        assert!(!toml("*.rs", "src/main.rs"));
+        assert!(toml("src/**/*.rs", "src/utils/time.rs"));
+        assert!(toml("a\\*b", "a*b"));
+        assert!(!toml("a\\*b", "axb"));
+
+        let dep5 =
+            |pattern: &str, path: &str| glob_match(pattern.as_bytes(), path.as_bytes(), true);
+        assert!(dep5("*", "src/main.rs"));
+        assert!(dep5("src/*.rs", "src/utils/time.rs"));
+        assert!(dep5("?.rs", "a.rs"));
+    }
+
+    #[test]
+    fn scan_headers() {
+        let info = scan("// SPDX-FileCopyrightText: 2024 Jane Doe\n//\n// SPDX-License-Identifier: MIT OR Apache-2.0\n");
+        assert!(info.copyright);
+        assert_eq!(info.licenses, vec!["MIT OR Apache-2.0"]);
+
+        let info = scan("/* SPDX-License-Identifier: MIT */\n");
+        assert!(!info.copyright);
+        assert_eq!(info.licenses, vec!["MIT"]);
+
+        let info = scan("# Copyright (c) 2024 Jane Doe\n");
+        assert!(info.copyright);
+    }
+
+    #[test]
+    fn expression_ids() {
+        let ids: Vec<&str> = license_ids("MIT OR (Apache-2.0+ WITH LLVM-exception)").collect();
+        assert_eq!(ids, vec!["MIT", "Apache-2.0", "LLVM-exception"]);
+        assert_eq!(check_id("MIT"), Some(false));
+        assert_eq!(check_id("GPL-2.0"), Some(true));
+        assert_eq!(check_id("LicenseRef-Acme"), Some(false));
+        assert_eq!(check_id("Foo"), None);
+    }
+}
@@ -0,0 +1,537 @@
+//! SPDX license expressions.
+
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+// Trove classifiers used by PyPI for the most common licenses
+const PYPI_CLASSIFIERS: &[(&str, &str)] = &[
+    ("0BSD", "License :: OSI Approved :: Zero-Clause BSD (0BSD)"),
+    (
+        "AFL-3.0",
+        "License :: OSI Approved :: Academic Free License (AFL)",
+    ),
+    (
+        "AGPL-3.0",
+        "License :: OSI Approved :: GNU Affero General Public License v3",
+    ),
+    (
+        "AGPL-3.0-only",
+        "License :: OSI Approved :: GNU Affero General Public License v3",
+    ),
+    (
+        "AGPL-3.0-or-later",
+        "License :: OSI Approved :: GNU Affero General Public License v3 or later (AGPLv3+)",
+    ),
+    (
+        "Apache-2.0",
+        "License :: OSI Approved :: Apache Software License",
+    ),
+    (
+        "Artistic-2.0",
+        "License :: OSI Approved :: Artistic License",
+    ),
+    ("BSD-1-Clause", "License :: OSI Approved :: BSD License"),
+    ("BSD-2-Clause", "License :: OSI Approved :: BSD License"),
+    ("BSD-3-Clause", "License :: OSI Approved :: BSD License"),
+    (
+        "BSL-1.0",
+        "License :: OSI Approved :: Boost Software License 1.0 (BSL-1.0)",
+    ),
+    (
+        "CC0-1.0",
+        "License :: CC0 1.0 Universal (CC0 1.0) Public Domain Dedication",
+    ),
+    (
+        "EPL-1.0",
+        "License :: OSI Approved :: Eclipse Public License 1.0 (EPL-1.0)",
+    ),
+    (
+        "EPL-2.0",
+        "License :: OSI Approved :: Eclipse Public License 2.0 (EPL-2.0)",
+    ),
+    (
+        "EUPL-1.0",
+        "License :: OSI Approved :: European Union Public Licence 1.0 (EUPL 1.0)",
+    ),
+    (
+        "EUPL-1.1",
+        "License :: OSI Approved :: European Union Public Licence 1.1 (EUPL 1.1)",
+    ),
+    (
+        "EUPL-1.2",
+        "License :: OSI Approved :: European Union Public Licence 1.2 (EUPL 1.2)",
+    ),
+    (
+        "GPL-2.0",
+        "License :: OSI Approved :: GNU General Public License v2 (GPLv2)",
+    ),
+    (
+        "GPL-2.0-only",
+        "License :: OSI Approved :: GNU General Public License v2 (GPLv2)",
+    ),
+    (
+        "GPL-2.0-or-later",
+        "License :: OSI Approved :: GNU General Public License v2 or later (GPLv2+)",
+    ),
+    (
+        "GPL-3.0",
+        "License :: OSI Approved :: GNU General Public License v3 (GPLv3)",
+    ),
+    (
+        "GPL-3.0-only",
+        "License :: OSI Approved :: GNU General Public License v3 (GPLv3)",
+    ),
+    (
+        "GPL-3.0-or-later",
+        "License :: OSI Approved :: GNU General Public License v3 or later (GPLv3+)",
+    ),
+    ("ISC", "License :: OSI Approved :: ISC License (ISCL)"),
+    (
+        "LGPL-2.0-only",
+        "License :: OSI Approved :: GNU Library or Lesser General Public License (LGPL)",
+    ),
+    (
+        "LGPL-2.0-or-later",
+        "License :: OSI Approved :: GNU Lesser General Public License v2 or later (LGPLv2+)",
+    ),
+    (
+        "LGPL-2.1",
+        "License :: OSI Approved :: GNU Lesser General Public License v2 (LGPLv2)",
+    ),
+    (
+        "LGPL-2.1-only",
+        "License :: OSI Approved :: GNU Lesser General Public License v2 (LGPLv2)",
+    ),
+    (
+        "LGPL-2.1-or-later",
+        "License :: OSI Approved :: GNU Lesser General Public License v2 or later (LGPLv2+)",
+    ),
+    (
+        "LGPL-3.0",
+        "License :: OSI Approved :: GNU Lesser General Public License v3 (LGPLv3)",
+    ),
+    (
+        "LGPL-3.0-only",
+        "License :: OSI Approved :: GNU Lesser General Public License v3 (LGPLv3)",
+    ),
+    (
+        "LGPL-3.0-or-later",
+        "License :: OSI Approved :: GNU Lesser General Public License v3 or later (LGPLv3+)",
+    ),
+    ("MIT", "License :: OSI Approved :: MIT License"),
+    (
+        "MIT-0",
+        "License :: OSI Approved :: MIT No Attribution License (MIT-0)",
+    ),
+    (
+        "MPL-1.0",
+        "License :: OSI Approved :: Mozilla Public License 1.0 (MPL)",
+    ),
+    (
+        "MPL-1.1",
+        "License :: OSI Approved :: Mozilla Public License 1.1 (MPL 1.1)",
+    ),
+    (
+        "MPL-2.0",
+        "License :: OSI Approved :: Mozilla Public License 2.0 (MPL 2.0)",
+    ),
+    (
+        "PSF-2.0",
+        "License :: OSI Approved :: Python Software Foundation License",
+    ),
+    (
+        "UPL-1.0",
+        "License :: OSI Approved :: Universal Permissive License (UPL)",
+    ),
+    (
+        "Unlicense",
+        "License :: OSI Approved :: The Unlicense (Unlicense)",
+    ),
+    ("Zlib", "License :: OSI Approved :: zlib/libpng License"),
+];
+
+// Prefix of the identifiers of licenses not included in the SPDX list
+const LICENSE_REF: &str = "LicenseRef-";
+
+/// A license not included in the SPDX list, e.g. a proprietary license.
+#[derive(Debug, Clone)]
+pub struct CustomLicense {
+    id: String,
+    text: String,
+    header: Option<String>,
+}
+
+impl CustomLicense {
+    /// Creates a new custom license from its `LicenseRef-<name>` identifier
+    /// and its text.
+    pub fn new(id: &str, text: &str) -> Result<Self> {
+        let valid = id.strip_prefix(LICENSE_REF).is_some_and(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        });
+        if !valid {
+            return Err(Error::InvalidLicenseRef(id.to_owned()));
+        }
+
+        Ok(Self {
+            id: id.to_owned(),
+            text: text.to_owned(),
+            header: None,
+        })
+    }
+
+    /// Creates a new custom license reading its text from a file.
+    pub fn from_file(id: &str, path: &Path) -> Result<Self> {
+        Self::new(id, &read_to_string(path)?)
+    }
+
+    /// Sets the header quoted in source files in place of the whole text.
+    pub fn header(mut self, header: &str) -> Self {
+        self.header = Some(header.to_owned());
+        self
+    }
+}
+
+/// A license referenced by a license expression.
+#[derive(Debug, Clone)]
+pub enum ProjectLicense {
+    /// A license included in the SPDX list
+    Spdx(&'static dyn license::License),
+    /// A `LicenseRef-<name>` license
+    Custom(CustomLicense),
+}
+
+impl ProjectLicense {
+    /// Returns the license identifier.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Spdx(license) => license.id(),
+            Self::Custom(license) => &license.id,
+        }
+    }
+
+    /// Returns the full name of the license.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Spdx(license) => license.name(),
+            Self::Custom(license) => &license.id,
+        }
+    }
+
+    /// Returns the license text.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Spdx(license) => license.text(),
+            Self::Custom(license) => &license.text,
+        }
+    }
+
+    /// Returns the license header, if any.
+    pub fn header(&self) -> Option<&str> {
+        match self {
+            Self::Spdx(license) => license.header(),
+            Self::Custom(license) => license.header.as_deref(),
+        }
+    }
+
+    // Retrieve the PyPI classifier of a license, falling back to the generic
+    // classifiers when the license has not a dedicated one
+    pub(crate) fn pypi_classifier(&self) -> &'static str {
+        match self {
+            Self::Spdx(license) => PYPI_CLASSIFIERS
+                .iter()
+                .find(|(id, _)| *id == license.id())
+                .map(|(_, classifier)| *classifier)
+                .unwrap_or(if license.is_osi_approved() {
+                    "License :: OSI Approved"
+                } else {
+                    "License :: Other/Proprietary License"
+                }),
+            Self::Custom(_) => "License :: Other/Proprietary License",
+        }
+    }
+
+    // Retrieve the page describing a license on the SPDX website
+    pub(crate) fn url(&self) -> Option<String> {
+        match self {
+            Self::Spdx(license) => Some(format!("https://spdx.org/licenses/{}.html", license.id())),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+/// A parsed SPDX license expression, e.g. `MIT OR Apache-2.0`.
+#[derive(Debug)]
+pub struct LicenseExpression {
+    expression: String,
+    licenses: Vec<ProjectLicense>,
+    exceptions: Vec<&'static dyn license::Exception>,
+}
+
+impl LicenseExpression {
+    /// Parses an SPDX license expression.
+    pub fn parse(expression: &str) -> Result<Self> {
+        Self::parse_with(expression, &[])
+    }
+
+    /// Parses an SPDX license expression which can also reference the given
+    /// custom licenses.
+    pub fn parse_with(expression: &str, custom_licenses: &[CustomLicense]) -> Result<Self> {
+        let tokens = tokenize(expression);
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            custom_licenses,
+            licenses: Vec::new(),
+            exceptions: Vec::new(),
+        };
+
+        let normalized = parser.or_expression()?;
+        if parser.position != tokens.len() {
+            return Err(Error::InvalidLicenseExpression(expression.to_owned()));
+        }
+
+        Ok(Self {
+            expression: normalized,
+            licenses: parser.licenses,
+            exceptions: parser.exceptions,
+        })
+    }
+
+    /// Returns the normalized expression.
+    pub fn id(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the licenses referenced by the expression, without duplicates.
+    pub fn licenses(&self) -> &[ProjectLicense] {
+        &self.licenses
+    }
+
+    /// Returns the exceptions referenced by the expression, without duplicates.
+    pub fn exceptions(&self) -> &[&'static dyn license::Exception] {
+        &self.exceptions
+    }
+}
+
+impl FromStr for LicenseExpression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+// Splits an expression into parentheses and words
+fn tokenize(expression: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, c) in expression.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(start) = start.take() {
+                tokens.push(&expression[start..index]);
+            }
+            if !c.is_whitespace() {
+                tokens.push(&expression[index..index + 1]);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(&expression[start..]);
+    }
+    tokens
+}
+
+// A recursive descent parser following the SPDX grammar, where `WITH` binds
+// tighter than `AND`, which binds tighter than `OR`
+struct Parser<'a> {
+    tokens: &'a [&'a str],
+    position: usize,
+    custom_licenses: &'a [CustomLicense],
+    licenses: Vec<ProjectLicense>,
+    exceptions: Vec<&'static dyn license::Exception>,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    // Consumes the next token when it is the given operator
+    fn operator(&mut self, operator: &str) -> bool {
+        let found = self
+            .tokens
+            .get(self.position)
+            .is_some_and(|token| *token == operator || *token == operator.to_lowercase());
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or_expression(&mut self) -> Result<String> {
+        let mut expression = self.and_expression()?;
+        while self.operator("OR") {
+            expression = format!("{expression} OR {}", self.and_expression()?);
+        }
+        Ok(expression)
+    }
+
+    fn and_expression(&mut self) -> Result<String> {
+        let mut expression = self.with_expression()?;
+        while self.operator("AND") {
+            expression = format!("{expression} AND {}", self.with_expression()?);
+        }
+        Ok(expression)
+    }
+
+    fn with_expression(&mut self) -> Result<String> {
+        let expression = self.simple_expression()?;
+        if self.operator("WITH") {
+            let id = self.next().ok_or_else(|| self.error())?;
+            let exception = id.parse::<&dyn license::Exception>()?;
+            if !self.exceptions.iter().any(|e| e.id() == exception.id()) {
+                self.exceptions.push(exception);
+            }
+            Ok(format!("{expression} WITH {id}"))
+        } else {
+            Ok(expression)
+        }
+    }
+
+    fn simple_expression(&mut self) -> Result<String> {
+        match self.next() {
+            Some("(") => {
+                let expression = self.or_expression()?;
+                match self.next() {
+                    Some(")") => Ok(format!("({expression})")),
+                    _ => Err(self.error()),
+                }
+            }
+            Some(")") | None => Err(self.error()),
+            Some(id) if id.starts_with(LICENSE_REF) => {
+                let license = self
+                    .custom_licenses
+                    .iter()
+                    .find(|license| license.id == id)
+                    .ok_or_else(|| Error::UnknownLicenseRef(id.to_owned()))?;
+                self.add_license(ProjectLicense::Custom(license.clone()));
+                Ok(id.to_owned())
+            }
+            Some(id) => {
+                // The `+` suffix means "this version or any later version"
+                let license = id
+                    .parse::<&dyn license::License>()
+                    .or_else(|e| id.strip_suffix('+').map_or(Err(e), str::parse))?;
+                self.add_license(ProjectLicense::Spdx(license));
+                Ok(id.to_owned())
+            }
+        }
+    }
+
+    fn add_license(&mut self, license: ProjectLicense) {
+        if !self.licenses.iter().any(|l| l.id() == license.id()) {
+            self.licenses.push(license);
+        }
+    }
+
+    fn error(&self) -> Error {
+        Error::InvalidLicenseExpression(self.tokens.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pypi_classifiers_are_valid_ids() {
+        for (id, _) in PYPI_CLASSIFIERS {
+            assert!(id.parse::<&dyn license::License>().is_ok(), "{id}");
+        }
+    }
+
+    #[test]
+    fn pypi_classifier_fallback() {
+        let license = ProjectLicense::Spdx("Beerware".parse().unwrap());
+        assert_eq!(
+            license.pypi_classifier(),
+            "License :: Other/Proprietary License"
+        );
+    }
+
+    #[test]
+    fn single_license() {
+        let expression = LicenseExpression::parse("MIT").unwrap();
+        assert_eq!(expression.id(), "MIT");
+        assert_eq!(expression.licenses().len(), 1);
+    }
+
+    #[test]
+    fn dual_license() {
+        let expression = LicenseExpression::parse("MIT  or (Apache-2.0)").unwrap();
+        assert_eq!(expression.id(), "MIT OR (Apache-2.0)");
+        let ids: Vec<&str> = expression.licenses().iter().map(|l| l.id()).collect();
+        assert_eq!(ids, ["MIT", "Apache-2.0"]);
+    }
+
+    #[test]
+    fn license_with_exception() {
+        let expression =
+            LicenseExpression::parse("(MIT AND Apache-2.0 WITH LLVM-exception) OR MIT").unwrap();
+        assert_eq!(expression.licenses().len(), 2);
+        assert_eq!(expression.exceptions()[0].id(), "LLVM-exception");
+    }
+
+    #[test]
+    fn or_later_license() {
+        let expression = LicenseExpression::parse("Apache-2.0+").unwrap();
+        assert_eq!(expression.id(), "Apache-2.0+");
+        assert_eq!(expression.licenses()[0].id(), "Apache-2.0");
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for expression in ["", "MIT OR", "(MIT", "MIT Apache-2.0", "MIT WITH MIT"] {
+            assert!(
+                LicenseExpression::parse(expression).is_err(),
+                "{expression}"
+            );
+        }
+        assert!(matches!(
+            LicenseExpression::parse("MIT OR Foo"),
+            Err(Error::InvalidLicense(_))
+        ));
+    }
+
+    #[test]
+    fn custom_license() {
+        let custom = CustomLicense::new("LicenseRef-Acme", "Acme license text").unwrap();
+        let expression =
+            LicenseExpression::parse_with("LicenseRef-Acme OR MIT", &[custom]).unwrap();
+        assert_eq!(expression.licenses()[0].text(), "Acme license text");
+        assert!(matches!(
+            LicenseExpression::parse("LicenseRef-Acme"),
+            Err(Error::UnknownLicenseRef(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_custom_license() {
+        for id in ["Acme", "LicenseRef-", "LicenseRef-Acme_1"] {
+            assert!(matches!(
+                CustomLicense::new(id, "text"),
+                Err(Error::InvalidLicenseRef(_))
+            ));
+        }
+    }
+}
@@ -1,25 +1,64 @@
 use minijinja::value::Value;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs::{read_to_string, write};
 use std::path::{Path, PathBuf};
 
+use tracing::warn;
+
 use crate::{
-    builtin_templates, command::run_command, error::Result, BuildTemplate, CreateCi, ProjectOutput,
-    TemplateData,
+    builtin_templates, command::run_command, error::Result, names, spdx::LicenseExpression,
+    BuildTemplate, CreateCi, Metadata, ProjectOutput, TemplateData,
 };
 
 use super::create_toolchain;
 
+// Licenses accepted for dependencies regardless of the project license
+const DENY_LICENSES: &[&str] = &[
+    "Apache-2.0",
+    "Apache-2.0 WITH LLVM-exception",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "MIT",
+    "Unicode-3.0",
+    "Unicode-DFS-2016",
+    "Zlib",
+];
+
+// cargo-binstall metadata matching the archives produced by the release workflow
+const BINSTALL_METADATA: &str = r#"
+[package.metadata.binstall]
+pkg-url = "{ repo }/releases/download/v{ version }/{ name }-{ version }-{ target }.tar.gz"
+bin-dir = "{ name }-{ version }-{ target }/{ bin }{ binary-ext }"
+pkg-fmt = "tgz"
+
+[package.metadata.binstall.overrides.x86_64-pc-windows-msvc]
+pkg-url = "{ repo }/releases/download/v{ version }/{ name }-{ version }-{ target }.zip"
+pkg-fmt = "zip"
+"#;
+
 static CARGO_TEMPLATES: &[(&str, &str)] = &builtin_templates!["cargo" =>
     ("md.README", "README.md"),
     ("rs.proptest", "proptest.rs"),
+    // Tools configuration
+    ("toml.clippy", "clippy.toml"),
+    ("toml.deny", "deny.toml"),
+    ("toml.rustfmt", "rustfmt.toml"),
+    ("toml.toolchain", "rust-toolchain.toml"),
+    ("toml.typos", "_typos.toml"),
     // GitHub
     ("ci.github", "github/test.yml"),
     ("ci.github.deploy", "github/deploy.yml"),
+    ("ci.github.release", "github/release.yml"),
     ("ci.github.publish.template", "github/publish-template.yml"),
     ("ci.github.publish", "github/publish.yml"),
     ("ci.github.docker", "github/docker-application.yml"),
+    ("ci.github.docker.prebuilt", "github/docker-application-prebuilt.yml"),
     ("ci.github.dependabot", "dependabot.yml"),
     // Dockerfile
+    ("docker.ignore", "dockerignore"),
+    ("docker.multistage", "Dockerfile"),
     ("docker.amd64", "Dockerfile-amd64"),
     ("docker.arm64", "Dockerfile-arm64"),
     // Execute checks and tests locally through xtask
@@ -30,6 +69,7 @@ static CARGO_TEMPLATES: &[(&str, &str)] = &builtin_templates!["cargo" =>
     ("xtask.src.dependencies", "xtask/src/dependencies.rs"),
     ("xtask.src.logging", "xtask/src/logging.rs"),
     ("xtask.src.main", "xtask/src/main.rs"),
+    ("xtask.src.msrv", "xtask/src/msrv.rs"),
     ("xtask.src.publish", "xtask/src/publish.rs"),
     ("xtask.src.runchecks", "xtask/src/runchecks.rs"),
     ("xtask.src.vulnerabilities", "xtask/src/vulnerabilities.rs"),
@@ -43,10 +83,78 @@ static CARGO_TEMPLATES: &[(&str, &str)] = &builtin_templates!["cargo" =>
     ("xtask.src.utils.workspace", "xtask/src/utils/workspace.rs")
 ];
 
+/// How the docker images of a cargo project are built.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DockerBuild {
+    /// A multi-stage Dockerfile building a static binary, pushed as a
+    /// multi-platform image through buildx
+    #[default]
+    MultiStage,
+    /// A Dockerfile for each architecture, copying a binary previously
+    /// built through cross
+    Prebuilt,
+}
+
+/// Container registry where docker images are pushed.
+#[derive(Debug, Clone, Default)]
+pub enum Registry<'a> {
+    /// GitHub Container Registry
+    #[default]
+    Ghcr,
+    /// Docker Hub
+    DockerHub,
+    /// Quay
+    Quay,
+    /// A registry reachable at a custom host
+    Custom(&'a str),
+}
+
+impl<'a> Registry<'a> {
+    // Host of the registry
+    fn host(&self) -> &'a str {
+        match self {
+            Self::Ghcr => "ghcr.io",
+            Self::DockerHub => "docker.io",
+            Self::Quay => "quay.io",
+            Self::Custom(host) => host,
+        }
+    }
+
+    // Secrets containing the registry username and password, the GitHub
+    // token is used for GHCR
+    fn secrets(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Ghcr => None,
+            Self::DockerHub => Some(("DOCKERHUB_USERNAME", "DOCKERHUB_TOKEN")),
+            Self::Quay => Some(("QUAY_USERNAME", "QUAY_TOKEN")),
+            Self::Custom(_) => Some(("REGISTRY_USERNAME", "REGISTRY_PASSWORD")),
+        }
+    }
+}
+
+/// Tags assigned to the pushed docker images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageTag {
+    /// The version of the git tag, e.g. 1.2.3 and 1.2
+    Semver,
+    /// The short commit hash
+    Sha,
+    /// The latest tag
+    Latest,
+}
+
 /// A cargo project data.
 #[derive(Default)]
 pub struct Cargo<'a> {
     docker_image_description: &'a str,
+    docker_build: DockerBuild,
+    docker_registry: Registry<'a>,
+    docker_image_name: Option<&'a str>,
+    docker_tags: &'a [ImageTag],
+    msrv: Option<&'a str>,
+    release: bool,
     ci: bool,
     lib: bool,
 }
@@ -62,6 +170,12 @@ impl<'a> Cargo<'a> {
     pub fn new() -> Self {
         Self {
             docker_image_description: "default",
+            docker_build: DockerBuild::MultiStage,
+            docker_registry: Registry::Ghcr,
+            docker_image_name: None,
+            docker_tags: &[ImageTag::Semver, ImageTag::Sha, ImageTag::Latest],
+            msrv: None,
+            release: false,
             lib: false,
             ci: false,
         }
@@ -73,19 +187,58 @@ impl<'a> Cargo<'a> {
         self
     }
 
+    /// Sets how docker images are built
+    pub fn docker_build(mut self, docker_build: DockerBuild) -> Self {
+        self.docker_build = docker_build;
+        self
+    }
+
+    /// Sets the registry where docker images are pushed
+    pub fn docker_registry(mut self, docker_registry: Registry<'a>) -> Self {
+        self.docker_registry = docker_registry;
+        self
+    }
+
+    /// Sets the docker image name, e.g. `organization/project`
+    pub fn docker_image_name(mut self, docker_image_name: &'a str) -> Self {
+        self.docker_image_name = Some(docker_image_name);
+        self
+    }
+
+    /// Sets the tags assigned to docker images
+    pub fn docker_tags(mut self, docker_tags: &'a [ImageTag]) -> Self {
+        self.docker_tags = docker_tags;
+        self
+    }
+
+    /// Sets the minimum supported Rust version
+    ///
+    /// With [`Cargo::only_ci`], the version is only checked by the CI and
+    /// `rust-version` has to be added to the existing `Cargo.toml` by hand.
+    pub fn msrv(mut self, msrv: &'a str) -> Self {
+        self.msrv = Some(msrv);
+        self
+    }
+
+    /// Sets a release workflow publishing binaries for several targets
+    pub fn release_binaries(mut self) -> Self {
+        self.release = true;
+        self
+    }
+
     /// Sets a library project
     pub fn create_lib(mut self) -> Self {
         self.lib = true;
         self
     }
 
-    /// Sets just ci files
+    /// Sets just ci files, leaving `Cargo.toml` untouched
     pub fn only_ci(mut self) -> Self {
         self.ci = true;
         self
     }
 
-    fn project_creation(&self, path: &Path) -> Result<()> {
+    fn project_creation(&self, path: &Path, metadata: &Metadata) -> Result<()> {
         if !self.ci {
             if self.lib {
                 run_command(path, &["init", "--lib"])?;
@@ -96,14 +249,109 @@ impl<'a> Cargo<'a> {
                 &path.join("Cargo.toml"),
                 &["add", "--dev", "proptest", "--manifest-path"],
             )?;
+            Cargo::add_package_fields(&path.join("Cargo.toml"), self.msrv, metadata)?;
+            if self.release {
+                Cargo::add_binstall_metadata(&path.join("Cargo.toml"))?;
+            }
+        } else {
+            // The manifest of an existing project is not modified
+            if let Some(msrv) = self.msrv {
+                warn!("Add `rust-version = \"{msrv}\"` to the `[package]` section of Cargo.toml to declare the minimum supported Rust version");
+            }
+            if self.release {
+                warn!("Add these sections to Cargo.toml to let cargo-binstall download the released binaries:{BINSTALL_METADATA}");
+            }
+        }
+        Ok(())
+    }
+
+    // Fills in the `[package]` section of the manifest with the minimum
+    // supported Rust version and the project metadata
+    fn add_package_fields(
+        manifest_path: &Path,
+        msrv: Option<&str>,
+        metadata: &Metadata,
+    ) -> Result<()> {
+        // Values are serialized as TOML strings, escaping quotes and newlines
+        let string = |value: &str| toml::Value::String(value.to_owned());
+        let mut fields = Vec::new();
+        if let Some(msrv) = msrv {
+            fields.push(format!("rust-version = {}", string(msrv)));
+        }
+        if let Some(description) = metadata.description {
+            fields.push(format!("description = {}", string(description)));
         }
+        if !metadata.authors.is_empty() {
+            let authors: Vec<toml::Value> = metadata
+                .authors
+                .iter()
+                .map(|author| string(&author.to_string()))
+                .collect();
+            fields.push(format!("authors = {}", toml::Value::Array(authors)));
+        }
+        if let Some(homepage) = metadata.homepage {
+            fields.push(format!("homepage = {}", string(homepage)));
+        }
+        if let Some(repository) = metadata.repository {
+            fields.push(format!("repository = {}", string(repository)));
+        }
+
+        let manifest = read_to_string(manifest_path)?;
+        let mut lines: Vec<String> = manifest.lines().map(str::to_owned).collect();
+
+        // Replace the version set by `cargo init`
+        if let Some(version) = metadata.version {
+            if let Some(line) = lines.iter_mut().find(|line| line.starts_with("version")) {
+                *line = format!("version = {}", string(version));
+            }
+        }
+
+        // Place the fields right after the `edition` field
+        let position = lines
+            .iter()
+            .position(|line| line.starts_with("edition"))
+            .or_else(|| lines.iter().position(|line| line == "[package]"))
+            .map_or(lines.len(), |position| position + 1);
+        lines.splice(position..position, fields);
+
+        write(manifest_path, lines.join("\n") + "\n")?;
+        Ok(())
+    }
+
+    // Lets cargo-binstall download the binaries published by the release workflow
+    fn add_binstall_metadata(manifest_path: &Path) -> Result<()> {
+        let mut manifest = read_to_string(manifest_path)?;
+        manifest.push_str(BINSTALL_METADATA);
+        write(manifest_path, manifest)?;
         Ok(())
     }
 
+    // Licenses allowed by cargo-deny, the project licenses are always accepted
+    fn deny_licenses(license: &LicenseExpression) -> Vec<&str> {
+        let mut licenses = DENY_LICENSES.to_vec();
+        for license in license.licenses() {
+            if !licenses.contains(&license.id()) {
+                licenses.push(license.id());
+            }
+        }
+        licenses.sort_unstable();
+        licenses
+    }
+
+    // Docker image name prefixed by the registry host, the repository owner
+    // is used as namespace when no image name is given
+    fn docker_image(&self, name: &str) -> String {
+        let host = self.docker_registry.host();
+        match self.docker_image_name {
+            Some(image_name) => format!("{host}/{image_name}"),
+            None => format!("{host}/${{{{ github.repository_owner }}}}/{name}"),
+        }
+    }
+
     fn project_structure(
+        &self,
         project_path: &Path,
         name: &str,
-        ci: bool,
     ) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>) {
         let root = project_path.to_path_buf();
         let github = project_path.join(".github");
@@ -122,13 +370,20 @@ impl<'a> Cargo<'a> {
         // dependabot
         template_files.insert(github.join("dependabot.yml"), "ci.github.dependabot");
 
+        // Tools configuration
+        template_files.insert(root.join("clippy.toml"), "toml.clippy");
+        template_files.insert(root.join("deny.toml"), "toml.deny");
+        template_files.insert(root.join("rustfmt.toml"), "toml.rustfmt");
+        template_files.insert(root.join("rust-toolchain.toml"), "toml.toolchain");
+        template_files.insert(root.join("_typos.toml"), "toml.typos");
+
         // Continuous Integration
         template_files.insert(workflows.join(format!("{name}.yml")), "ci.github");
-        template_files.insert(
-            workflows.join(format!("{name}-docker-application.yml")),
-            "ci.github.docker",
-        );
-        template_files.insert(workflows.join("deploy.yml"), "ci.github.deploy");
+        if self.release {
+            template_files.insert(workflows.join("release.yml"), "ci.github.release");
+        } else {
+            template_files.insert(workflows.join("deploy.yml"), "ci.github.deploy");
+        }
         template_files.insert(
             workflows.join("publish-template.yml"),
             "ci.github.publish.template",
@@ -136,8 +391,19 @@ impl<'a> Cargo<'a> {
         template_files.insert(workflows.join("publish.yml"), "ci.github.publish");
 
         // Docker
-        template_files.insert(docker.join("Dockerfile-amd64"), "docker.amd64");
-        template_files.insert(docker.join("Dockerfile-arm64"), "docker.arm64");
+        let docker_workflow = workflows.join(format!("{name}-docker-application.yml"));
+        match self.docker_build {
+            DockerBuild::MultiStage => {
+                template_files.insert(docker_workflow, "ci.github.docker");
+                template_files.insert(docker.join("Dockerfile"), "docker.multistage");
+                template_files.insert(root.join(".dockerignore"), "docker.ignore");
+            }
+            DockerBuild::Prebuilt => {
+                template_files.insert(docker_workflow, "ci.github.docker.prebuilt");
+                template_files.insert(docker.join("Dockerfile-amd64"), "docker.amd64");
+                template_files.insert(docker.join("Dockerfile-arm64"), "docker.arm64");
+            }
+        }
 
         // xtask configuration file for cargo
         template_files.insert(cargo.join("config"), "xtask.config");
@@ -149,6 +415,9 @@ impl<'a> Cargo<'a> {
         template_files.insert(xtask_src.join("dependencies.rs"), "xtask.src.dependencies");
         template_files.insert(xtask_src.join("logging.rs"), "xtask.src.logging");
         template_files.insert(xtask_src.join("main.rs"), "xtask.src.main");
+        if self.msrv.is_some() {
+            template_files.insert(xtask_src.join("msrv.rs"), "xtask.src.msrv");
+        }
         template_files.insert(xtask_src.join("publish.rs"), "xtask.src.publish");
         template_files.insert(xtask_src.join("runchecks.rs"), "xtask.src.runchecks");
         template_files.insert(
@@ -167,7 +436,7 @@ impl<'a> Cargo<'a> {
             "xtask.src.utils.workspace",
         );
 
-        if !ci {
+        if !self.ci {
             // Proptest
             let tests = project_path.join("tests");
             template_files.insert(tests.join("proptest.rs"), "rs.proptest");
@@ -189,31 +458,71 @@ impl<'a> BuildTemplate for Cargo<'a> {
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput> {
         let mut context = HashMap::new();
 
         context.insert("name", Value::from_serializable(&project_name));
         context.insert("branch", Value::from_serializable(&github_branch));
-        context.insert("license_id", Value::from_serializable(&license));
+        context.insert("license_id", Value::from_serializable(&license.id()));
+        context.insert("metadata", Value::from_serializable(metadata));
+        context.insert("msrv", Value::from_serializable(&self.msrv));
+        context.insert("release", Value::from_serializable(&self.release));
+        context.insert(
+            "deny_licenses",
+            Value::from_serializable(&Cargo::deny_licenses(license)),
+        );
         context.insert(
             "docker_image_description",
             Value::from_serializable(&self.docker_image_description),
         );
+        context.insert("docker_build", Value::from_serializable(&self.docker_build));
+        context.insert(
+            "docker_image",
+            Value::from_serializable(&self.docker_image(project_name)),
+        );
+        context.insert(
+            "docker_registry",
+            Value::from_serializable(&self.docker_registry.host()),
+        );
+        context.insert(
+            "docker_secrets",
+            Value::from_serializable(&self.docker_registry.secrets()),
+        );
+        context.insert("docker_tags", Value::from_serializable(&self.docker_tags));
 
-        Cargo::project_creation(self, project_path)?;
+        let names = HashMap::from([("crate_name", names::crate_name(project_name)?)]);
+        context.insert("names", Value::from_serializable(&names));
 
-        let (files, dirs) = Cargo::project_structure(project_path, project_name, self.ci);
+        let (files, dirs) = self.project_structure(project_path, project_name);
 
         Ok(ProjectOutput {
             files,
             dirs,
             context,
+            file_contexts: HashMap::new(),
         })
     }
 
     fn get_templates() -> &'static [(&'static str, &'static str)] {
         CARGO_TEMPLATES
     }
+
+    fn validate_name(&self, name: &str) -> Result<()> {
+        names::crate_name(name).map(|_| ())
+    }
+
+    fn external_files(&self) -> &'static [&'static str] {
+        match (self.ci, self.lib) {
+            (true, _) => &[],
+            (false, true) => &["Cargo.toml", "Cargo.lock", ".gitignore", "src/lib.rs"],
+            (false, false) => &["Cargo.toml", "Cargo.lock", ".gitignore", "src/main.rs"],
+        }
+    }
+
+    fn init_project(&self, project_path: &Path, metadata: &Metadata) -> Result<()> {
+        self.project_creation(project_path, metadata)
+    }
 }
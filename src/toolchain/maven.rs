@@ -2,9 +2,16 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use minijinja::value::Value;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    builtin_templates, error::Result, BuildTemplate, CreateProject, ProjectOutput, TemplateData,
+    builtin_templates,
+    error::{Error, Result},
+    filters::{convert_case, Case},
+    names,
+    spdx::LicenseExpression,
+    BuildTemplate, CreateProject, FileContexts, Metadata, ProjectOutput, TemplateData,
+    VendoredFiles,
 };
 
 use super::create_toolchain;
@@ -13,19 +20,142 @@ static MAVEN_TEMPLATES: &[(&str, &str)] = &builtin_templates!["maven" =>
     ("java.entry", "Entry.java"),
     ("java.example", "Example.java"),
     ("xml.pom", "pom.xml"),
+    ("xml.pom.module", "module.pom.xml"),
+    ("xml.pom.coverage", "coverage.pom.xml"),
     ("xml.checkstyle", "checkstyle.xml"),
     ("md.README", "README.md"),
     ("ci.github", "github.yml"),
-    ("ci.github.dependabot", "dependabot.yml")
+    ("ci.github.dependabot", "dependabot.yml"),
+    ("ci.github.release", "release.yml"),
+    ("sh.mvnw", "mvnw"),
+    ("cmd.mvnw", "mvnw.cmd"),
+    ("properties.wrapper", "maven-wrapper.properties")
 ];
 
+// Maven release run by the wrapper and required by the enforcer plugin
+const MAVEN_VERSION: &str = "3.9.9";
+const WRAPPER_VERSION: &str = "3.3.2";
+
+// The wrapper scripts are copied from the Apache Maven Wrapper project
+const WRAPPER_FILES: &[VendoredFiles] = &[VendoredFiles {
+    paths: &["mvnw", "mvnw.cmd", ".mvn/wrapper/**"],
+    copyright: "The Apache Software Foundation",
+    license: "Apache-2.0",
+}];
+
 const MAIN: &str = "main/java";
 const TESTS: &str = "test/java";
+// Module aggregating the coverage reports of a multi-module project
+const COVERAGE_MODULE: &str = "coverage";
+
+/// Java release targeted by a maven project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum JavaVersion {
+    /// Java 11
+    Java11,
+    /// Java 17
+    #[default]
+    Java17,
+    /// Java 21
+    Java21,
+}
+
+impl JavaVersion {
+    const ALL: [JavaVersion; 3] = [Self::Java11, Self::Java17, Self::Java21];
+
+    // Releases tested by the CI, from the targeted one to the newest
+    fn matrix(self) -> Vec<u8> {
+        Self::ALL
+            .iter()
+            .filter(|version| **version >= self)
+            .map(|version| u8::from(*version))
+            .collect()
+    }
+}
+
+impl From<JavaVersion> for u8 {
+    fn from(version: JavaVersion) -> Self {
+        match version {
+            JavaVersion::Java11 => 11,
+            JavaVersion::Java17 => 17,
+            JavaVersion::Java21 => 21,
+        }
+    }
+}
+
+impl TryFrom<u8> for JavaVersion {
+    type Error = String;
+
+    fn try_from(version: u8) -> std::result::Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|v| u8::from(*v) == version)
+            .ok_or_else(|| format!("{version} is not a supported Java release"))
+    }
+}
+
+/// Test framework of a maven project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum JUnit {
+    /// JUnit 4
+    JUnit4,
+    /// JUnit 5, also known as JUnit Jupiter
+    #[default]
+    JUnit5,
+}
+
+impl From<JUnit> for u8 {
+    fn from(junit: JUnit) -> Self {
+        match junit {
+            JUnit::JUnit4 => 4,
+            JUnit::JUnit5 => 5,
+        }
+    }
+}
+
+impl TryFrom<u8> for JUnit {
+    type Error = String;
+
+    fn try_from(version: u8) -> std::result::Result<Self, Self::Error> {
+        match version {
+            4 => Ok(Self::JUnit4),
+            5 => Ok(Self::JUnit5),
+            _ => Err(format!("{version} is not a supported JUnit version")),
+        }
+    }
+}
+
+/// Quality plugins of a maven project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MavenPlugin {
+    /// Style checks through Checkstyle
+    Checkstyle,
+    /// Formatting checks through Spotless and google-java-format
+    Spotless,
+    /// Bug patterns detection through SpotBugs
+    SpotBugs,
+    /// Static analysis through PMD
+    Pmd,
+    /// Code coverage through JaCoCo
+    Jacoco,
+}
+
+const DEFAULT_PLUGINS: &[MavenPlugin] = &[MavenPlugin::Checkstyle, MavenPlugin::Jacoco];
 
 #[derive(Default)]
 /// A maven project.
 pub struct Maven<'a> {
     group: &'a str,
+    java: JavaVersion,
+    junit: JUnit,
+    plugins: &'a [MavenPlugin],
+    coverage_threshold: Option<u8>,
+    modules: &'a [&'a str],
+    release: bool,
+    release_repository: Option<&'a str>,
 }
 
 impl<'a> CreateProject for Maven<'a> {
@@ -36,23 +166,94 @@ impl<'a> CreateProject for Maven<'a> {
 impl<'a> Maven<'a> {
     /// Creates a new `Maven` instance.
     pub fn new() -> Self {
-        Self { group: "group" }
+        Self {
+            group: "group",
+            plugins: DEFAULT_PLUGINS,
+            ..Default::default()
+        }
     }
 
-    /// Sets a group
+    /// Sets a group, e.g. `com.example`, each of its components is a
+    /// directory of the package
     pub fn group(mut self, group: &'a str) -> Self {
         self.group = group;
         self
     }
 
+    /// Sets the targeted Java release
+    pub fn java(mut self, java: JavaVersion) -> Self {
+        self.java = java;
+        self
+    }
+
+    /// Sets the test framework
+    pub fn junit(mut self, junit: JUnit) -> Self {
+        self.junit = junit;
+        self
+    }
+
+    /// Sets the enabled quality plugins, checkstyle and jacoco by default
+    pub fn plugins(mut self, plugins: &'a [MavenPlugin]) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Sets the minimum line coverage, as a percentage, checked by jacoco
+    pub fn coverage_threshold(mut self, coverage_threshold: u8) -> Self {
+        self.coverage_threshold = Some(coverage_threshold);
+        self
+    }
+
+    /// Sets the modules of a multi-module project, the project becomes
+    /// their aggregator
+    pub fn modules(mut self, modules: &'a [&'a str]) -> Self {
+        self.modules = modules;
+        self
+    }
+
+    /// Sets a release workflow deploying signed artifacts to Maven Central
+    /// when a version tag is pushed
+    pub fn release(mut self) -> Self {
+        self.release = true;
+        self
+    }
+
+    /// Sets the repository the release workflow deploys to in place of
+    /// Maven Central, enabling the workflow
+    pub fn release_repository(mut self, url: &'a str) -> Self {
+        self.release = true;
+        self.release_repository = Some(url);
+        self
+    }
+
+    // Sources of a module, or of the whole project when it has no modules
+    fn sources(
+        template_files: &mut HashMap<PathBuf, &'static str>,
+        module_path: &Path,
+        package_path: &str,
+        name: &str,
+    ) -> Vec<PathBuf> {
+        let main = module_path.join(format!("src/{MAIN}/{package_path}"));
+        let tests = module_path.join(format!("src/{TESTS}/{package_path}/example"));
+
+        // All files in the main directory, the entry class is named after the project
+        let entry = format!("{}.java", convert_case(name, Case::Pascal));
+        template_files.insert(main.join(entry), "java.entry");
+
+        // All files in the test directory
+        template_files.insert(tests.join("Example.java"), "java.example");
+
+        vec![main, tests]
+    }
+
     fn project_structure(
+        &self,
         project_path: &Path,
-        group: &str,
+        package_path: &str,
         name: &str,
-    ) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>) {
+        modules: &[(&str, String)],
+    ) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>, FileContexts) {
         let root = project_path.to_path_buf();
-        let main = project_path.join(format!("src/{MAIN}/{group}/{name}"));
-        let tests = project_path.join(format!("src/{TESTS}/{group}/{name}/example"));
         let github = project_path.join(".github");
         let workflows = github.join("workflows");
 
@@ -60,23 +261,77 @@ impl<'a> Maven<'a> {
 
         // All the files in the root of the projects
         template_files.insert(root.join("pom.xml"), "xml.pom");
-        template_files.insert(root.join("checkstyle.xml"), "xml.checkstyle");
+        if self.plugins.contains(&MavenPlugin::Checkstyle) {
+            template_files.insert(root.join("checkstyle.xml"), "xml.checkstyle");
+        }
         template_files.insert(root.join("README.md"), "md.README");
         template_files.insert(root.join("LICENSE.md"), "build.license");
 
-        // All files in the main directory
-        template_files.insert(main.join("Entry.java"), "java.entry");
+        // Maven wrapper, the project is built without a system maven
+        let wrapper = root.join(".mvn/wrapper");
+        template_files.insert(root.join("mvnw"), "sh.mvnw");
+        template_files.insert(root.join("mvnw.cmd"), "cmd.mvnw");
+        template_files.insert(
+            wrapper.join("maven-wrapper.properties"),
+            "properties.wrapper",
+        );
 
-        // All files in the test directory
-        template_files.insert(tests.join("Example.java"), "java.example");
+        let mut dirs = vec![root.clone(), wrapper];
+        let mut file_contexts = HashMap::new();
+        if modules.is_empty() {
+            dirs.extend(Self::sources(
+                &mut template_files,
+                project_path,
+                package_path,
+                name,
+            ));
+        }
+
+        // Each module has its own pom and sources, rendered with the name
+        // and package of the module
+        for (module, package) in modules {
+            let module_path = root.join(module);
+            let module_package_path = package.replace('.', "/");
+            let names =
+                HashMap::from([("package", package), ("package_path", &module_package_path)]);
+            let mut module_files = HashMap::new();
+            module_files.insert(module_path.join("pom.xml"), "xml.pom.module");
+            dirs.extend(Self::sources(
+                &mut module_files,
+                &module_path,
+                &module_package_path,
+                module,
+            ));
+
+            let module_context = HashMap::from([
+                ("name", Value::from_serializable(module)),
+                ("parent", Value::from_serializable(&name)),
+                ("names", Value::from_serializable(&names)),
+            ]);
+            for path in module_files.keys() {
+                file_contexts.insert(path.clone(), module_context.clone());
+            }
+            template_files.extend(module_files);
+        }
+
+        if !modules.is_empty() && self.plugins.contains(&MavenPlugin::Jacoco) {
+            let coverage = root.join(COVERAGE_MODULE);
+            template_files.insert(coverage.join("pom.xml"), "xml.pom.coverage");
+            dirs.push(coverage);
+        }
 
         // dependabot
         template_files.insert(github.join("dependabot.yml"), "ci.github.dependabot");
 
         // Continuous integration files
         template_files.insert(workflows.join(format!("{name}.yml")), "ci.github");
+        if self.release {
+            template_files.insert(workflows.join("release.yml"), "ci.github.release");
+        }
 
-        (template_files, vec![root, main, tests, workflows])
+        dirs.push(workflows);
+
+        (template_files, dirs, file_contexts)
     }
 }
 
@@ -85,26 +340,122 @@ impl<'a> BuildTemplate for Maven<'a> {
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput> {
         let mut context = HashMap::new();
 
         context.insert("name", Value::from_serializable(&project_name));
         context.insert("branch", Value::from_serializable(&github_branch));
         context.insert("group", Value::from_serializable(&self.group));
-        context.insert("license_id", Value::from_serializable(&license));
+        context.insert("license_id", Value::from_serializable(&license.id()));
+        context.insert("metadata", Value::from_serializable(metadata));
+
+        // Java sources are placed in the directory of their package
+        let package = format!("{}.{}", self.group, names::java_package(project_name)?);
+        let package_path = package.replace('.', "/");
+        let names = HashMap::from([("package", &package), ("package_path", &package_path)]);
+        context.insert("names", Value::from_serializable(&names));
 
-        let (files, dirs) = Maven::project_structure(project_path, self.group, project_name);
+        context.insert("maven_version", Value::from_serializable(&MAVEN_VERSION));
+        context.insert(
+            "wrapper_version",
+            Value::from_serializable(&WRAPPER_VERSION),
+        );
+        context.insert("java", Value::from_serializable(&self.java));
+        context.insert("java_matrix", Value::from_serializable(&self.java.matrix()));
+        context.insert("junit", Value::from_serializable(&self.junit));
+        context.insert("plugins", Value::from_serializable(&self.plugins));
+        // Jacoco expects the minimum coverage as a ratio
+        let coverage_threshold = self
+            .coverage_threshold
+            .filter(|_| self.plugins.contains(&MavenPlugin::Jacoco));
+        let coverage_ratio =
+            coverage_threshold.map(|threshold| format!("{:.2}", f32::from(threshold) / 100.0));
+        context.insert(
+            "coverage_threshold",
+            Value::from_serializable(&coverage_threshold),
+        );
+        context.insert("coverage_ratio", Value::from_serializable(&coverage_ratio));
+
+        // The package of a module is nested in the one of the project
+        let modules = self
+            .modules
+            .iter()
+            .map(|module| {
+                Ok((
+                    *module,
+                    format!("{package}.{}", names::java_package(module)?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut aggregated = self.modules.to_vec();
+        if !modules.is_empty() && self.plugins.contains(&MavenPlugin::Jacoco) {
+            aggregated.push(COVERAGE_MODULE);
+        }
+        context.insert("modules", Value::from_serializable(&self.modules));
+
+        // Without a release repository, the Central Publisher Portal plugin
+        // deploys to Maven Central
+        context.insert("release", Value::from_serializable(&self.release));
+        context.insert(
+            "release_repository",
+            Value::from_serializable(&self.release_repository),
+        );
+        context.insert("aggregated_modules", Value::from_serializable(&aggregated));
+
+        let (files, dirs, file_contexts) =
+            self.project_structure(project_path, &package_path, project_name, &modules);
 
         Ok(ProjectOutput {
             files,
             dirs,
             context,
+            file_contexts,
         })
     }
 
     fn get_templates() -> &'static [(&'static str, &'static str)] {
         MAVEN_TEMPLATES
     }
+
+    fn vendored_files(&self) -> &'static [VendoredFiles] {
+        WRAPPER_FILES
+    }
+
+    fn validate_name(&self, name: &str) -> Result<()> {
+        names::java_group(self.group)?;
+        names::java_package(name)?;
+        for module in self.modules {
+            names::java_package(module)?;
+            // The name is reserved for the module generated when jacoco is enabled
+            if *module == COVERAGE_MODULE {
+                return Err(Error::InvalidName {
+                    name: COVERAGE_MODULE.to_owned(),
+                    reason: "the module aggregating the coverage reports has the same name"
+                        .to_owned(),
+                    suggestion: format!("{name}-{COVERAGE_MODULE}"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_options(&self) -> Result<()> {
+        // A repeated module would overwrite the files of the first one
+        for (i, module) in self.modules.iter().enumerate() {
+            if self.modules[..i].contains(module) {
+                return Err(Error::UnsupportedOption(format!(
+                    "the module {module} is listed more than once"
+                )));
+            }
+        }
+        if let Some(threshold) = self.coverage_threshold.filter(|&t| t > 100) {
+            return Err(Error::UnsupportedOption(format!(
+                "the coverage threshold {threshold} is not a percentage"
+            )));
+        }
+        Ok(())
+    }
 }
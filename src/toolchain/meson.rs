@@ -5,7 +5,11 @@ use minijinja::value::Value;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    builtin_templates, error::Result, BuildTemplate, CreateProject, ProjectOutput, TemplateData,
+    builtin_templates,
+    error::{Error, Result},
+    names,
+    spdx::LicenseExpression,
+    BuildTemplate, CreateProject, Metadata, ProjectOutput, TemplateData,
 };
 
 use super::create_toolchain;
@@ -26,35 +30,214 @@ static MESON_TEMPLATES: &[(&str, &str)] = &builtin_templates!["meson" =>
     ("run.tests", "run_tests.sh"),
     ("md.README", "README.md"),
     ("ci.github", "github.yml"),
-    ("ci.github.dependabot", "dependabot.yml")
+    ("ci.github.dependabot", "dependabot.yml"),
+    ("wrap.unity", "unity.wrap"),
+    ("wrap.criterion", "criterion.wrap"),
+    ("wrap.gtest", "gtest.wrap"),
+    ("wrap.catch2", "catch2.wrap"),
+    ("build.gtest", "gtest.build")
 ];
 
 /// Kind of a meson project.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ProjectKind {
     /// C-language project
     #[default]
+    #[serde(rename = "c")]
     C,
     /// C++-language project
+    #[serde(rename = "c++")]
     Cxx,
 }
 
-#[derive(Default)]
+impl ProjectKind {
+    // Standard used when none is set
+    fn default_standard(self) -> Standard {
+        match self {
+            Self::C => Standard::C17,
+            Self::Cxx => Standard::Cxx17,
+        }
+    }
+}
+
+/// Language standard of a meson project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Standard {
+    /// C11
+    #[serde(rename = "c11")]
+    C11,
+    /// C17
+    #[serde(rename = "c17")]
+    C17,
+    /// C23
+    #[serde(rename = "c23")]
+    C23,
+    /// C++17
+    #[serde(rename = "c++17")]
+    Cxx17,
+    /// C++20
+    #[serde(rename = "c++20")]
+    Cxx20,
+    /// C++23
+    #[serde(rename = "c++23")]
+    Cxx23,
+}
+
+impl Standard {
+    fn kind(self) -> ProjectKind {
+        match self {
+            Self::C11 | Self::C17 | Self::C23 => ProjectKind::C,
+            Self::Cxx17 | Self::Cxx20 | Self::Cxx23 => ProjectKind::Cxx,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::C11 => "c11",
+            Self::C17 => "c17",
+            Self::C23 => "c23",
+            Self::Cxx17 => "c++17",
+            Self::Cxx20 => "c++20",
+            Self::Cxx23 => "c++23",
+        }
+    }
+
+    // Oldest meson release knowing the standard
+    fn meson_version(self) -> MesonVersion {
+        match self {
+            Self::C11 | Self::C17 | Self::Cxx17 => (0, 49, 0),
+            Self::Cxx20 => (0, 57, 0),
+            Self::Cxx23 => (1, 0, 0),
+            Self::C23 => (1, 4, 0),
+        }
+    }
+}
+
+/// Unit-test framework of a meson project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TestFramework {
+    /// Unity, for C projects
+    #[serde(rename = "unity")]
+    Unity,
+    /// Criterion, for C projects
+    #[serde(rename = "criterion")]
+    Criterion,
+    /// GoogleTest, for C++ projects
+    #[serde(rename = "gtest")]
+    GoogleTest,
+    /// Catch2, for C++ projects
+    #[serde(rename = "catch2")]
+    Catch2,
+}
+
+impl TestFramework {
+    fn kind(self) -> ProjectKind {
+        match self {
+            Self::Unity | Self::Criterion => ProjectKind::C,
+            Self::GoogleTest | Self::Catch2 => ProjectKind::Cxx,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Unity => "unity",
+            Self::Criterion => "criterion",
+            Self::GoogleTest => "gtest",
+            Self::Catch2 => "catch2",
+        }
+    }
+}
+
+/// Targets built by a meson project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// A library, in `lib/`
+    Library,
+    /// An executable, in `cli/`
+    Executable,
+    /// A library and an executable linking it
+    #[default]
+    Both,
+}
+
+impl Layout {
+    fn library(self) -> bool {
+        self != Self::Executable
+    }
+
+    fn executable(self) -> bool {
+        self != Self::Library
+    }
+}
+
+/// Kind of the library built by a meson project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LibraryKind {
+    /// Shared library
+    #[default]
+    Shared,
+    /// Static library
+    Static,
+    /// Both a shared and a static library
+    Both,
+}
+
+impl LibraryKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Shared => "shared",
+            Self::Static => "static",
+            Self::Both => "both",
+        }
+    }
+}
+
+// Meson release as major, minor and patch numbers
+type MesonVersion = (u8, u8, u8);
+
+// Oldest meson release providing wraps and test protocols to the frameworks
+const TEST_FRAMEWORK_MESON_VERSION: MesonVersion = (0, 55, 0);
+
+const DEFAULT_WARNING_LEVEL: u8 = 3;
+const DEFAULT_FLAGS: &[&str] = &["-pedantic"];
+
 /// A meson project data.
-pub struct Meson {
+pub struct Meson<'a> {
     kind: ProjectKind,
+    standard: Option<Standard>,
+    warning_level: u8,
+    werror: bool,
+    flags: &'a [&'a str],
+    test_framework: Option<TestFramework>,
+    layout: Layout,
+    library_kind: Option<LibraryKind>,
 }
 
-impl CreateProject for Meson {
+impl<'a> Default for Meson<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> CreateProject for Meson<'a> {
     fn create_project(&self, data: TemplateData) -> Result<()> {
         create_toolchain(self, data)
     }
 }
-impl Meson {
+impl<'a> Meson<'a> {
     /// Creates a new `Meson` instance.
     pub fn new() -> Self {
         Self {
             kind: ProjectKind::C,
+            standard: None,
+            warning_level: DEFAULT_WARNING_LEVEL,
+            werror: false,
+            flags: DEFAULT_FLAGS,
+            test_framework: None,
+            layout: Layout::Both,
+            library_kind: None,
         }
     }
 
@@ -64,14 +247,56 @@ impl Meson {
         self
     }
 
+    /// Sets the language standard, c17 or c++17 by default
+    pub fn standard(mut self, standard: Standard) -> Self {
+        self.standard = Some(standard);
+        self
+    }
+
+    /// Sets the warning level, from 0 to 3, the default one
+    pub fn warning_level(mut self, warning_level: u8) -> Self {
+        self.warning_level = warning_level.min(3);
+        self
+    }
+
+    /// Treats warnings as errors
+    pub fn werror(mut self) -> Self {
+        self.werror = true;
+        self
+    }
+
+    /// Sets the compiler flags, only the ones supported by the compiler are
+    /// used, `-pedantic` by default
+    pub fn flags(mut self, flags: &'a [&'a str]) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the unit-test framework, tests are plain executables by default
+    pub fn test_framework(mut self, test_framework: TestFramework) -> Self {
+        self.test_framework = Some(test_framework);
+        self
+    }
+
+    /// Sets the built targets, a library and an executable by default
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the kind of the library, shared by default
+    pub fn library_kind(mut self, library_kind: LibraryKind) -> Self {
+        self.library_kind = Some(library_kind);
+        self
+    }
+
     // Build a map Path <-> template
     fn project_structure(
+        &self,
         project_path: &Path,
         name: &str,
         src_ext: &str,
     ) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>) {
-        let name = &name.replace('-', "_");
-
         let root = project_path.to_path_buf();
         let cli = project_path.join("cli");
         let lib = project_path.join("lib");
@@ -85,18 +310,50 @@ impl Meson {
         template_files.insert(root.join(MESON_FILE), "build.root");
         template_files.insert(root.join("README.md"), "md.README");
 
+        let mut dirs = vec![root.clone()];
+
         // All the files in the `cli/` directory of the project
-        template_files.insert(cli.join(MESON_FILE), "build.cli");
-        template_files.insert(cli.join(name).with_extension(src_ext), "source.bin");
+        if self.layout.executable() {
+            template_files.insert(cli.join(MESON_FILE), "build.cli");
+            template_files.insert(cli.join(name).with_extension(src_ext), "source.bin");
+            dirs.push(cli);
+        }
 
         // All the files in the `lib/` directory of the project
-        template_files.insert(lib.join(MESON_FILE), "build.lib");
-        template_files.insert(lib.join(name).with_extension("h"), "header");
-        template_files.insert(lib.join(name).with_extension(src_ext), "source.lib");
+        if self.layout.library() {
+            template_files.insert(lib.join(MESON_FILE), "build.lib");
+            template_files.insert(lib.join(name).with_extension("h"), "header");
+            template_files.insert(lib.join(name).with_extension(src_ext), "source.lib");
+            dirs.push(lib);
+        }
 
         // All the tests for the project, in `tests/`
         template_files.insert(tests.join(MESON_FILE), "build.test");
         template_files.insert(tests.join(name).with_extension(src_ext), "source.test");
+        dirs.push(tests);
+
+        // The test framework is fetched as a subproject
+        if let Some(test_framework) = self.test_framework {
+            let subprojects = root.join("subprojects");
+            let wrap = match test_framework {
+                TestFramework::Unity => "wrap.unity",
+                TestFramework::Criterion => "wrap.criterion",
+                TestFramework::GoogleTest => "wrap.gtest",
+                TestFramework::Catch2 => "wrap.catch2",
+            };
+            let wrap_file = subprojects
+                .join(test_framework.as_str())
+                .with_extension("wrap");
+            template_files.insert(wrap_file, wrap);
+
+            // GoogleTest is not built by meson, its build file is provided
+            if test_framework == TestFramework::GoogleTest {
+                let packagefiles = subprojects.join("packagefiles/gtest");
+                template_files.insert(packagefiles.join(MESON_FILE), "build.gtest");
+                dirs.push(packagefiles);
+            }
+            dirs.push(subprojects);
+        }
 
         // All docker files
         template_files.insert(root.join("Dockerfile"), "Dockerfile");
@@ -109,40 +366,116 @@ impl Meson {
         // Continuous integration files
         template_files.insert(workflows.join(format!("{name}.yml")), "ci.github");
 
-        (template_files, vec![root, cli, lib, tests, workflows])
+        dirs.push(workflows);
+
+        (template_files, dirs)
     }
 }
 
-impl BuildTemplate for Meson {
+impl<'a> BuildTemplate for Meson<'a> {
     fn define(
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput> {
         let mut context = HashMap::new();
-        let (ext, params) = match self.kind {
-            ProjectKind::C => ("c", "c_std=c99"),
-            ProjectKind::Cxx => ("cpp", "cpp_std=c++11"),
+        let ext = match self.kind {
+            ProjectKind::C => "c",
+            ProjectKind::Cxx => "cpp",
         };
+        let standard = self
+            .standard
+            .unwrap_or_else(|| self.kind.default_standard());
+
+        let mut default_options = vec![
+            format!("{ext}_std={}", standard.as_str()),
+            format!("warning_level={}", self.warning_level),
+        ];
+        if self.werror {
+            default_options.push("werror=true".to_owned());
+        }
+        if self.layout.library() {
+            let library_kind = self.library_kind.unwrap_or_default();
+            default_options.push(format!("default_library={}", library_kind.as_str()));
+        }
 
         context.insert("name", Value::from_serializable(&project_name));
         context.insert("branch", Value::from_serializable(&github_branch));
         context.insert("exe", Value::from_serializable(&ext));
-        context.insert("params", Value::from_serializable(&params));
-        context.insert("license_id", Value::from_serializable(&license));
+        context.insert(
+            "default_options",
+            Value::from_serializable(&default_options),
+        );
+        let mut meson_version = standard.meson_version();
+        if self.test_framework.is_some() {
+            meson_version = meson_version.max(TEST_FRAMEWORK_MESON_VERSION);
+        }
+        let (major, minor, patch) = meson_version;
+        context.insert(
+            "meson_version",
+            Value::from_serializable(&format!("{major}.{minor}.{patch}")),
+        );
+        context.insert("library", Value::from_serializable(&self.layout.library()));
+        context.insert(
+            "executable",
+            Value::from_serializable(&self.layout.executable()),
+        );
+        context.insert(
+            "test_framework",
+            Value::from_serializable(&self.test_framework),
+        );
+        context.insert("flags", Value::from_serializable(&self.flags));
+        context.insert("license_id", Value::from_serializable(&license.id()));
+        context.insert("metadata", Value::from_serializable(metadata));
+
+        // Source files and identifiers are named after the module
+        let module = names::c_identifier(project_name)?;
+        let names = HashMap::from([("module", &module)]);
+        context.insert("names", Value::from_serializable(&names));
 
-        let (files, dirs) = Meson::project_structure(project_path, project_name, ext);
+        let (files, dirs) = self.project_structure(project_path, &module, ext);
 
         Ok(ProjectOutput {
             context,
             files,
             dirs,
+            file_contexts: HashMap::new(),
         })
     }
 
     fn get_templates() -> &'static [(&'static str, &'static str)] {
         MESON_TEMPLATES
     }
+
+    fn validate_name(&self, name: &str) -> Result<()> {
+        names::c_identifier(name).map(|_| ())
+    }
+
+    fn validate_options(&self) -> Result<()> {
+        let language = match self.kind {
+            ProjectKind::C => "C",
+            ProjectKind::Cxx => "C++",
+        };
+        if let Some(standard) = self.standard.filter(|s| s.kind() != self.kind) {
+            return Err(Error::UnsupportedOption(format!(
+                "the {} standard cannot be used by a {language} project",
+                standard.as_str(),
+            )));
+        }
+        if self.library_kind.is_some() && !self.layout.library() {
+            return Err(Error::UnsupportedOption(
+                "the library kind cannot be set without a library".to_owned(),
+            ));
+        }
+        if let Some(framework) = self.test_framework.filter(|f| f.kind() != self.kind) {
+            return Err(Error::UnsupportedOption(format!(
+                "the {} test framework cannot be used by a {language} project",
+                framework.as_str(),
+            )));
+        }
+        Ok(())
+    }
 }
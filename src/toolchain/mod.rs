@@ -4,8 +4,10 @@ pub mod meson;
 pub mod poetry;
 pub mod yarn;
 
+use std::fs::create_dir_all;
+
 use crate::{
-    compute_template, define_license, error::Result, path_validation, BuildTemplate, TemplateData,
+    compute_template, define_license, error::Result, resolve_path, BuildTemplate, TemplateData,
 };
 
 macro_rules! builtin_templates {
@@ -24,8 +26,27 @@ macro_rules! builtin_templates {
 pub(crate) use builtin_templates;
 
 fn create_toolchain<T: BuildTemplate>(toolchain: &T, data: TemplateData) -> Result<()> {
-    let project_path = path_validation(data.project_path)?;
-    let license = define_license(data.license)?;
-    let template = toolchain.build(&project_path, data.name, license.id(), data.branch);
-    compute_template(template?, license, &project_path)
+    toolchain.validate_name(data.name)?;
+    toolchain.validate_options()?;
+    let project_path = resolve_path(data.project_path)?;
+    let license = define_license(data.license, &data.custom_licenses)?;
+    let mut template = toolchain.build(
+        &project_path,
+        data.name,
+        &license,
+        data.branch,
+        &data.metadata,
+    )?;
+    // Nothing is written until the custom extensions are known to be valid
+    template.install_extensions(&data.extensions)?;
+    create_dir_all(&project_path)?;
+    toolchain.init_project(&project_path, &data.metadata)?;
+    compute_template(
+        template,
+        &license,
+        &project_path,
+        &data,
+        toolchain.external_files(),
+        toolchain.vendored_files(),
+    )
 }
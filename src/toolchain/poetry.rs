@@ -4,7 +4,8 @@ use std::path::{Path, PathBuf};
 use minijinja::value::Value;
 
 use crate::{
-    builtin_templates, error::Result, BuildTemplate, CreateProject, ProjectOutput, TemplateData,
+    builtin_templates, error::Result, names, spdx::LicenseExpression, BuildTemplate, CreateProject,
+    Metadata, ProjectOutput, TemplateData,
 };
 
 use super::create_toolchain;
@@ -39,11 +40,12 @@ impl Poetry {
     fn project_structure(
         project_path: &Path,
         name: &str,
+        module: &str,
     ) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>) {
         let root = project_path.to_path_buf();
-        let main = project_path.join(name);
-        let data = project_path.join(format!("{name}/data"));
-        let tests = project_path.join(format!("{name}/tests"));
+        let main = project_path.join(module);
+        let data = project_path.join(format!("{module}/data"));
+        let tests = project_path.join(format!("{module}/tests"));
         let github = project_path.join(".github");
         let workflows = github.join("workflows");
 
@@ -78,25 +80,37 @@ impl BuildTemplate for Poetry {
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput> {
         let mut context = HashMap::new();
 
         context.insert("name", Value::from_serializable(&project_name));
         context.insert("branch", Value::from_serializable(&github_branch));
-        context.insert("license_id", Value::from_serializable(&license));
+        context.insert("license_id", Value::from_serializable(&license.id()));
+        context.insert("metadata", Value::from_serializable(metadata));
 
-        let (files, dirs) = Poetry::project_structure(project_path, project_name);
+        // The package directory is named after the module
+        let module = names::python_module(project_name)?;
+        let names = HashMap::from([("module", &module)]);
+        context.insert("names", Value::from_serializable(&names));
+
+        let (files, dirs) = Poetry::project_structure(project_path, project_name, &module);
 
         Ok(ProjectOutput {
             context,
             files,
             dirs,
+            file_contexts: HashMap::new(),
         })
     }
 
     fn get_templates() -> &'static [(&'static str, &'static str)] {
         POETRY_TEMPLATES
     }
+
+    fn validate_name(&self, name: &str) -> Result<()> {
+        names::python_module(name).map(|_| ())
+    }
 }
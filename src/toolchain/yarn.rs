@@ -4,7 +4,8 @@ use std::path::{Path, PathBuf};
 use minijinja::value::Value;
 
 use crate::{
-    builtin_templates, error::Result, BuildTemplate, CreateCi, ProjectOutput, TemplateData,
+    builtin_templates, error::Result, spdx::LicenseExpression, BuildTemplate, CreateCi, Metadata,
+    ProjectOutput, TemplateData,
 };
 
 use super::create_toolchain;
@@ -61,20 +62,23 @@ impl BuildTemplate for Yarn {
         &self,
         project_path: &Path,
         project_name: &str,
-        license: &str,
+        license: &LicenseExpression,
         github_branch: &str,
+        metadata: &Metadata,
     ) -> Result<ProjectOutput> {
         let mut context = HashMap::new();
 
         context.insert("name", Value::from_serializable(&project_name));
         context.insert("branch", Value::from_serializable(&github_branch));
-        context.insert("license_id", Value::from_serializable(&license));
+        context.insert("license_id", Value::from_serializable(&license.id()));
+        context.insert("metadata", Value::from_serializable(metadata));
 
         let (files, dirs) = Yarn::project_structure(project_path, project_name);
         Ok(ProjectOutput {
             context,
             files,
             dirs,
+            file_contexts: HashMap::new(),
         })
     }
 
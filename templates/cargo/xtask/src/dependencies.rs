@@ -77,7 +77,7 @@ fn cargo_deny() {
     group!("Cargo: run deny checks");
     run_cargo(
         "deny",
-        Params::from(["check"]),
+        Params::from(["check", "--config", "deny.toml"]),
         HashMap::new(),
         "Cargo deny should be installed and it should correctly run",
     );
@@ -3,6 +3,9 @@ use clap::{Parser, Subcommand};
 mod books;
 mod dependencies;
 mod logging;
+{%- if msrv %}
+mod msrv;
+{%- endif %}
 mod publish;
 mod runchecks;
 mod utils;
@@ -27,6 +30,10 @@ enum Command {
         /// The dependency check to run
         dependency_check: dependencies::DependencyCheck,
     },
+{%- if msrv %}
+    /// Check the crate against its minimum supported Rust version ({{ msrv }})
+    Msrv,
+{%- endif %}
     /// Publish a crate to crates.io
     Publish {
         /// The name of the crate to publish on crates.io
@@ -51,6 +58,9 @@ fn main() -> anyhow::Result<()> {
     match args.command {
         Command::Books(args) => args.parse(),
         Command::Dependencies { dependency_check } => dependency_check.run(),
+{%- if msrv %}
+        Command::Msrv => msrv::run(),
+{%- endif %}
         Command::Publish { name } => publish::run(name),
         Command::RunChecks { env } => env.run(),
         Command::Vulnerabilities {
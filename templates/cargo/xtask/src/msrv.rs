@@ -0,0 +1,51 @@
+//! This script checks the crate against its minimum supported Rust version.
+//!
+//! To run the script:
+//!
+//! cargo xtask msrv
+
+use std::{collections::HashMap, time::Instant};
+
+use crate::{
+    endgroup, group,
+    logging::init_logger,
+    utils::{cargo::run_cargo, rustup::rustup_install_toolchain, time::format_duration, Params},
+};
+
+// Minimum supported Rust version, it must match the `rust-version` field
+// contained in the manifest
+const MSRV: &str = "{{ msrv }}";
+
+pub(crate) fn run() -> anyhow::Result<()> {
+    // Setup logger
+    init_logger().init();
+
+    // Start time measurement
+    let start = Instant::now();
+
+    // Install the MSRV toolchain
+    rustup_install_toolchain(MSRV);
+
+    // Run cargo check with the MSRV toolchain
+    group!("Cargo: check against Rust {}", MSRV);
+    run_cargo(
+        "check",
+        Params::from(["--color=always"]),
+        HashMap::from([("RUSTUP_TOOLCHAIN", MSRV.to_string())]),
+        "Failed to run cargo check with the MSRV toolchain",
+    );
+    endgroup!();
+
+    // Stop time measurement
+    //
+    // Compute runtime duration
+    let duration = start.elapsed();
+
+    // Print duration
+    info!(
+        "\x1B[32;1mTime elapsed for the current execution: {}\x1B[0m",
+        format_duration(&duration)
+    );
+
+    Ok(())
+}
@@ -273,6 +273,7 @@ fn check_typos() {
 
     // Run typos command as child process
     let typos = Command::new("typos")
+        .args(["--config", "_typos.toml"])
         .stdout(Stdio::inherit()) // Send stdout directly to terminal
         .stderr(Stdio::inherit()) // Send stderr directly to terminal
         .spawn()
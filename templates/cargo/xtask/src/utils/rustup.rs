@@ -18,6 +18,19 @@ pub(crate) fn rustup(command: &str, params: Params, expected: &str) {
     handle_child_process(cargo_process, "Failed to wait for rustup child process");
 }
 
+{% if msrv -%}
+/// Install a Rust toolchain
+pub(crate) fn rustup_install_toolchain(toolchain: &str) {
+    group!("Rustup: install toolchain {}", toolchain);
+    rustup(
+        "toolchain",
+        Params::from(["install", toolchain, "--profile", "minimal"]),
+        "Toolchain should be installed",
+    );
+    endgroup!();
+}
+
+{% endif -%}
 /// Add a Rust target
 pub(crate) fn rustup_add_target(target: &str) {
     group!("Rustup: add target {}", target);
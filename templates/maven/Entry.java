@@ -1,8 +1,14 @@
-package {{ group }}.{{ name }};
+package {{ names.package }};
+
+public class {{ name | pascal_case }} {
+    private {{ name | pascal_case }}() {}
+
+    public static String greeting() {
+        return "Hello World!";
+    }
 
-public class Entry {
     public static void main(String[] args) {
-        System.out.println("Hello World!");
+        System.out.println(greeting());
     }
 }
 
@@ -1,10 +1,39 @@
-package {{ group }}.{{ name }}.example;
+package {{ names.package }}.example;
+{%- set entry = name | pascal_case %}
+{%- if junit == 4 %}
+{%- set imports = ["org.junit.Test", names.package ~ "." ~ entry] %}
+{%- else %}
+{%- set imports = ["org.junit.jupiter.api.Test", names.package ~ "." ~ entry] %}
+{%- endif %}
 
-import org.junit.Test;
+import static org.junit.{% if junit == 4 %}Assert{% else %}jupiter.api.Assertions{% endif %}.assertEquals;
 
+{% for import in imports | sort -%}
+import {{ import }};
+{% endfor %}
+{%- if junit == 4 %}
 public class Example {
     @Test
-    public void testExample() {
+    public void testGreeting() {
+        assertEquals("Hello World!", {{ entry }}.greeting());
+    }
+
+    @Test
+    public void testMain() {
+        {{ entry }}.main(new String[0]);
+    }
+}
+{%- else %}
+class Example {
+    @Test
+    void testGreeting() {
+        assertEquals("Hello World!", {{ entry }}.greeting());
+    }
+
+    @Test
+    void testMain() {
+        {{ entry }}.main(new String[0]);
     }
 }
+{%- endif %}
 
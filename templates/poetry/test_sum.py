@@ -1,7 +1,9 @@
 {{ name | comment_license("#") }}
-{% for line in license.text %}
+{% if license.licenses | length == 1 %}{% for line in license.text %}
 {{ line | comment_license("#") }}
-{% endfor %}
+{% endfor %}{% elif not spdx_headers %}
+{{ ("SPDX-License-Identifier: " ~ license.id) | comment_license("#") }}
+{% endif %}
 
 def test_sum():
     """Simple test."""
@@ -3,7 +3,10 @@ mod common;
 use std::env::temp_dir;
 use std::path::Path;
 
-use generate_ci::{cargo::Cargo, CreateCi, TemplateData};
+use generate_ci::{
+    cargo::{Cargo, DockerBuild, ImageTag, Registry},
+    CreateCi, TemplateData,
+};
 
 use common::compare_template_skip;
 
@@ -11,6 +14,10 @@ const SKIPPED_FOLDERS: &[&str] = &[".git", "Cargo.lock"];
 const SNAPSHOT_PATH_B: &str = "../repositories/snapshots/cargo/";
 const SNAPSHOT_PATH_L: &str = "../repositories/snapshots/cargo_library/";
 const SNAPSHOT_PATH_C: &str = "../repositories/snapshots/cargo_ci/";
+const SNAPSHOT_PATH_M: &str = "../repositories/snapshots/cargo_msrv/";
+const SNAPSHOT_PATH_D: &str = "../repositories/snapshots/cargo_docker_prebuilt/";
+const SNAPSHOT_PATH_R: &str = "../repositories/snapshots/cargo_release/";
+const SNAPSHOT_PATH_H: &str = "../repositories/snapshots/cargo_headers/";
 
 #[test]
 fn test_cargo_binary() {
@@ -69,3 +76,155 @@ fn test_cargo_ci() {
         .unwrap();
     compare_template_skip(Path::new(SNAPSHOT_PATH_C), &path, SKIPPED_FOLDERS);
 }
+
+#[test]
+fn test_cargo_docker_prebuilt() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_docker_prebuilt");
+    let data = TemplateData::new(&path, "cargo-rust-docker")
+        .license("MIT")
+        .branch("main");
+
+    Cargo::new()
+        .docker_image_description("description-docker")
+        .docker_build(DockerBuild::Prebuilt)
+        .docker_registry(Registry::Quay)
+        .docker_image_name("organization/cargo-rust-docker")
+        .docker_tags(&[ImageTag::Semver, ImageTag::Sha])
+        .only_ci()
+        .create_ci(data)
+        .unwrap();
+    compare_template_skip(Path::new(SNAPSHOT_PATH_D), &path, SKIPPED_FOLDERS);
+}
+
+#[test]
+fn test_cargo_msrv() {
+    // Rust nightly version can introduce changes making tests fail, so this
+    // test is not executed on nightly
+    if std::env::var("RUSTUP_TOOLCHAIN").map_or(true, |env| env.starts_with("nightly")) {
+        return;
+    }
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_msrv");
+    let data = TemplateData::new(&path, "cargo-rust-msrv")
+        .license("MIT")
+        .branch("main");
+
+    Cargo::new()
+        .docker_image_description("description-docker")
+        .msrv("1.85")
+        .create_ci(data)
+        .unwrap();
+    compare_template_skip(Path::new(SNAPSHOT_PATH_M), &path, SKIPPED_FOLDERS);
+}
+
+#[test]
+fn test_cargo_release() {
+    // Rust nightly version can introduce changes making tests fail, so this
+    // test is not executed on nightly
+    if std::env::var("RUSTUP_TOOLCHAIN").map_or(true, |env| env.starts_with("nightly")) {
+        return;
+    }
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_release");
+    let data = TemplateData::new(&path, "cargo-rust-release")
+        .license("MIT")
+        .branch("main")
+        .description("A cargo project publishing its binaries")
+        .author("Jane Doe <jane.doe@example.org>")
+        .repository("https://github.com/example/cargo-rust-release")
+        .version("1.2.3");
+
+    Cargo::new()
+        .docker_image_description("description-docker")
+        .release_binaries()
+        .create_ci(data)
+        .unwrap();
+    compare_template_skip(Path::new(SNAPSHOT_PATH_R), &path, SKIPPED_FOLDERS);
+}
+
+#[test]
+fn test_cargo_headers() {
+    // Rust nightly version can introduce changes making tests fail, so this
+    // test is not executed on nightly
+    if std::env::var("RUSTUP_TOOLCHAIN").map_or(true, |env| env.starts_with("nightly")) {
+        return;
+    }
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_headers");
+    let data = TemplateData::new(&path, "cargo-rust-headers")
+        .license("MIT OR Apache-2.0")
+        .branch("main")
+        .author("Jane Doe <jane.doe@example.org>")
+        .year(2024)
+        .spdx_headers();
+
+    Cargo::new()
+        .docker_image_description("description-docker")
+        .create_ci(data)
+        .unwrap();
+    compare_template_skip(Path::new(SNAPSHOT_PATH_H), &path, SKIPPED_FOLDERS);
+}
+
+#[test]
+fn test_cargo_headers_regenerated() {
+    // Rust nightly version can introduce changes making tests fail, so this
+    // test is not executed on nightly
+    if std::env::var("RUSTUP_TOOLCHAIN").map_or(true, |env| env.starts_with("nightly")) {
+        return;
+    }
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_headers_regenerated");
+    let generate = || {
+        let data = TemplateData::new(&path, "cargo-rust-headers")
+            .author("Jane Doe <jane.doe@example.org>")
+            .year(2024)
+            .spdx_headers();
+        Cargo::new().create_ci(data).unwrap();
+    };
+
+    generate();
+    // The sources of the project are kept while it is generated again
+    std::fs::remove_file(path.join("Cargo.toml")).unwrap();
+    std::fs::remove_file(path.join("Cargo.lock")).unwrap();
+    generate();
+
+    let main = std::fs::read_to_string(path.join("src/main.rs")).unwrap();
+    assert_eq!(main.matches("SPDX-License-Identifier").count(), 1);
+}
+
+#[test]
+fn test_cargo_metadata_escaped() {
+    // Rust nightly version can introduce changes making tests fail, so this
+    // test is not executed on nightly
+    if std::env::var("RUSTUP_TOOLCHAIN").map_or(true, |env| env.starts_with("nightly")) {
+        return;
+    }
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("cargo_escaped");
+    let _ = std::fs::remove_dir_all(&path);
+    let data = TemplateData::new(&path, "cargo-escaped")
+        .description("The \"best\" tool\nfor C:\\ paths")
+        .author("Jane \"JD\" Doe <jane.doe@example.org>");
+    Cargo::new().create_ci(data).unwrap();
+
+    // The manifest stays valid and keeps the values as given
+    let manifest: toml::Table = std::fs::read_to_string(path.join("Cargo.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let package = &manifest["package"];
+    assert_eq!(
+        package["description"].as_str(),
+        Some("The \"best\" tool\nfor C:\\ paths")
+    );
+    assert_eq!(
+        package["authors"][0].as_str(),
+        Some("Jane \"JD\" Doe <jane.doe@example.org>")
+    );
+}
@@ -0,0 +1,46 @@
+use std::env::temp_dir;
+
+use generate_ci::{cargo::Cargo, error::Error, yarn::Yarn, CreateCi, TemplateData};
+
+#[test]
+fn test_custom_extensions() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("extensions_custom");
+    let data = TemplateData::new(&path, "yarn-extensions")
+        .filter("shout", |value: String| value.to_uppercase())
+        .test("short", |value: String| value.len() < 5)
+        .function("git_remote", || {
+            "https://github.com/example/yarn-extensions"
+        });
+
+    assert!(Yarn::new().create_ci(data).is_ok());
+}
+
+#[test]
+fn test_builtin_collision() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("extensions_collision");
+    let data = TemplateData::new(&path, "yarn-extensions").filter("comment_license", |v: String| v);
+
+    let error = Yarn::new().create_ci(data).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::TemplateExtension { kind: "filter", name } if name == "comment_license"
+    ));
+    // Collisions are found before the project is created
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_context_collision() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("extensions_context_collision");
+    let data = TemplateData::new(&path, "cargo-extensions").function("license", || "MIT");
+
+    let error = Cargo::new().create_ci(data).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::TemplateExtension { kind: "function", name } if name == "license"
+    ));
+    assert!(!path.exists());
+}
@@ -0,0 +1,95 @@
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read_to_string, remove_dir_all, write};
+use std::path::PathBuf;
+
+use generate_ci::headers::{update_headers, HeaderStatus, SpdxHeader};
+
+#[test]
+fn test_update_headers() {
+    let path = temp_dir().join("headers_update");
+    let _ = remove_dir_all(&path);
+    create_dir_all(path.join("src")).unwrap();
+    create_dir_all(path.join("LICENSES")).unwrap();
+    write(path.join("src/main.rs"), "fn main() {}\n").unwrap();
+    write(path.join("run.sh"), "#!/bin/sh\necho\n").unwrap();
+    write(path.join("data.json"), "{}\n").unwrap();
+    write(path.join("LICENSES/MIT.txt"), "MIT License\n").unwrap();
+
+    let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe <jane.doe@example.org>");
+
+    // Check mode does not modify files
+    let statuses = update_headers(&path, &header, true).unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            (PathBuf::from("data.json"), HeaderStatus::Unsupported),
+            (PathBuf::from("run.sh"), HeaderStatus::Missing),
+            (PathBuf::from("src/main.rs"), HeaderStatus::Missing),
+        ]
+    );
+    assert_eq!(
+        read_to_string(path.join("src/main.rs")).unwrap(),
+        "fn main() {}\n"
+    );
+
+    update_headers(&path, &header, false).unwrap();
+    assert_eq!(
+        read_to_string(path.join("src/main.rs")).unwrap(),
+        "// SPDX-FileCopyrightText: 2024 Jane Doe <jane.doe@example.org>\n//\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n"
+    );
+    assert!(read_to_string(path.join("run.sh"))
+        .unwrap()
+        .starts_with("#!/bin/sh\n# SPDX-FileCopyrightText"));
+
+    // Headers are updated only once
+    let statuses = update_headers(&path, &header, true).unwrap();
+    assert!(statuses
+        .iter()
+        .all(|(_, status)| matches!(status, HeaderStatus::Current | HeaderStatus::Unsupported)));
+
+    let header = SpdxHeader::new("MIT").copyright("2025 John Doe");
+    let statuses = update_headers(&path, &header, false).unwrap();
+    assert!(statuses.contains(&(PathBuf::from("src/main.rs"), HeaderStatus::Outdated)));
+    let content = read_to_string(path.join("src/main.rs")).unwrap();
+    assert!(content.contains("2024 Jane Doe"));
+    assert!(content.contains("2025 John Doe"));
+}
+
+#[test]
+fn test_update_headers_foreign_license() {
+    let path = temp_dir().join("headers_foreign");
+    let _ = remove_dir_all(&path);
+    create_dir_all(path.join("vendor")).unwrap();
+    let vendored = "/*\n * SPDX-FileCopyrightText: 2020 Upstream Author\n *\n * SPDX-License-Identifier: BSD-3-Clause\n */\n\nint x;\n";
+    write(path.join("vendor/lib.c"), vendored).unwrap();
+
+    // The vendored file keeps its license and copyright
+    let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe");
+    let statuses = update_headers(&path, &header, false).unwrap();
+    assert_eq!(
+        statuses,
+        vec![(PathBuf::from("vendor/lib.c"), HeaderStatus::Conflict)]
+    );
+    assert_eq!(read_to_string(path.join("vendor/lib.c")).unwrap(), vendored);
+}
+
+#[test]
+fn test_update_headers_ignored() {
+    // Outside a git work tree, the .gitignore files are still honoured
+    let path = temp_dir().join("headers_ignored");
+    let _ = remove_dir_all(&path);
+    create_dir_all(path.join("src")).unwrap();
+    create_dir_all(path.join("target/debug")).unwrap();
+    write(path.join(".gitignore"), "/target\n").unwrap();
+    write(path.join("src/main.rs"), "fn main() {}\n").unwrap();
+    write(path.join("target/debug/build.rs"), "fn main() {}\n").unwrap();
+
+    let header = SpdxHeader::new("MIT").copyright("2024 Jane Doe");
+    let statuses = update_headers(&path, &header, false).unwrap();
+    assert!(statuses.contains(&(PathBuf::from("src/main.rs"), HeaderStatus::Missing)));
+    assert!(!statuses.iter().any(|(file, _)| file.starts_with("target")));
+    assert_eq!(
+        read_to_string(path.join("target/debug/build.rs")).unwrap(),
+        "fn main() {}\n"
+    );
+}
@@ -0,0 +1,216 @@
+use std::env::temp_dir;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use generate_ci::{
+    maven::Maven,
+    meson::{Meson, ProjectKind},
+    poetry::Poetry,
+    spdx::CustomLicense,
+    CreateProject, ReuseFormat, TemplateData,
+};
+
+// Licenses paired with their PyPI classifier
+const LICENSES: &[(&str, &str)] = &[
+    (
+        "Apache-2.0",
+        "License :: OSI Approved :: Apache Software License",
+    ),
+    ("BSD-3-Clause", "License :: OSI Approved :: BSD License"),
+    (
+        "GPL-3.0-or-later",
+        "License :: OSI Approved :: GNU General Public License v3 or later (GPLv3+)",
+    ),
+    (
+        "LGPL-2.1-only",
+        "License :: OSI Approved :: GNU Lesser General Public License v2 (LGPLv2)",
+    ),
+    ("MIT", "License :: OSI Approved :: MIT License"),
+    (
+        "MPL-2.0",
+        "License :: OSI Approved :: Mozilla Public License 2.0 (MPL 2.0)",
+    ),
+    ("NCSA", "License :: OSI Approved"),
+    ("Beerware", "License :: Other/Proprietary License"),
+];
+
+fn read(path: &Path) -> String {
+    read_to_string(path).unwrap_or_else(|_| panic!("Unable to read {}", path.display()))
+}
+
+fn license_name(id: &str) -> &'static str {
+    id.parse::<&dyn license::License>().unwrap().name()
+}
+
+#[test]
+fn test_poetry_licenses() {
+    for (id, classifier) in LICENSES {
+        let path = temp_dir().join(format!("license_poetry_{id}"));
+        let data = TemplateData::new(&path, "poetry-license").license(id);
+        Poetry::new().create_project(data).unwrap();
+
+        let pyproject = read(&path.join("pyproject.toml"));
+        assert!(pyproject.contains(&format!("license = \"{id}\"")), "{id}");
+        assert!(pyproject.contains(&format!("\"{classifier}\",")), "{id}");
+        assert!(path.join("LICENSES").join(format!("{id}.txt")).is_file());
+        assert!(
+            read(&path.join("REUSE.toml")).contains(&format!("SPDX-License-Identifier = \"{id}\""))
+        );
+    }
+}
+
+#[test]
+fn test_maven_licenses() {
+    for (id, _) in LICENSES {
+        let path = temp_dir().join(format!("license_maven_{id}"));
+        let data = TemplateData::new(&path, "maven-license").license(id);
+        Maven::new().create_project(data).unwrap();
+
+        let pom = read(&path.join("pom.xml"));
+        assert!(
+            pom.contains(&format!("<name>{}</name>", license_name(id))),
+            "{id}"
+        );
+        assert!(
+            pom.contains(&format!("<url>https://spdx.org/licenses/{id}.html</url>")),
+            "{id}"
+        );
+        assert!(path.join("LICENSES").join(format!("{id}.txt")).is_file());
+        assert!(
+            read(&path.join("REUSE.toml")).contains(&format!("SPDX-License-Identifier = \"{id}\""))
+        );
+    }
+}
+
+#[test]
+fn test_meson_licenses() {
+    for (id, _) in LICENSES {
+        let path = temp_dir().join(format!("license_meson_{id}"));
+        let data = TemplateData::new(&path, "meson-license").license(id);
+        Meson::new()
+            .kind(ProjectKind::C)
+            .create_project(data)
+            .unwrap();
+
+        let build = read(&path.join("meson.build"));
+        assert!(build.contains(&format!("license: '{id}'")), "{id}");
+        assert!(path.join("LICENSES").join(format!("{id}.txt")).is_file());
+        assert!(
+            read(&path.join("REUSE.toml")).contains(&format!("SPDX-License-Identifier = \"{id}\""))
+        );
+    }
+}
+
+#[test]
+fn test_license_expression() {
+    let expression = "MIT OR (Apache-2.0 WITH LLVM-exception)";
+
+    let path = temp_dir().join("license_poetry_expression");
+    let data = TemplateData::new(&path, "poetry-license").license(expression);
+    Poetry::new().create_project(data).unwrap();
+
+    let pyproject = read(&path.join("pyproject.toml"));
+    assert!(pyproject.contains(&format!("license = \"{expression}\"")));
+    assert!(pyproject.contains("\"License :: OSI Approved :: MIT License\","));
+    assert!(pyproject.contains("\"License :: OSI Approved :: Apache Software License\","));
+    for id in ["MIT", "Apache-2.0", "LLVM-exception"] {
+        assert!(path.join("LICENSES").join(format!("{id}.txt")).is_file());
+    }
+    assert!(read(&path.join("REUSE.toml"))
+        .contains(&format!("SPDX-License-Identifier = \"{expression}\"")));
+
+    let path = temp_dir().join("license_maven_expression");
+    let data = TemplateData::new(&path, "maven-license").license(expression);
+    Maven::new().create_project(data).unwrap();
+
+    let pom = read(&path.join("pom.xml"));
+    for id in ["MIT", "Apache-2.0"] {
+        assert!(pom.contains(&format!("<name>{}</name>", license_name(id))));
+    }
+}
+
+#[test]
+fn test_custom_license() {
+    let expression = "LicenseRef-Acme OR MIT";
+    let acme = CustomLicense::new("LicenseRef-Acme", "Acme license text")
+        .unwrap()
+        .header("Licensed under the Acme license");
+
+    let path = temp_dir().join("license_poetry_custom");
+    let data = TemplateData::new(&path, "poetry-license")
+        .license(expression)
+        .custom_license(acme.clone());
+    Poetry::new().create_project(data).unwrap();
+
+    let pyproject = read(&path.join("pyproject.toml"));
+    assert!(pyproject.contains("\"License :: Other/Proprietary License\","));
+    assert_eq!(
+        read(&path.join("LICENSES").join("LicenseRef-Acme.txt")),
+        "Acme license text"
+    );
+    assert!(read(&path.join("LICENSE.md")).starts_with("Acme license text"));
+    assert!(read(&path.join("REUSE.toml"))
+        .contains(&format!("SPDX-License-Identifier = \"{expression}\"")));
+
+    let path = temp_dir().join("license_maven_custom");
+    let data = TemplateData::new(&path, "maven-license")
+        .license("LicenseRef-Acme")
+        .custom_license(acme);
+    Maven::new().create_project(data).unwrap();
+
+    let pom = read(&path.join("pom.xml"));
+    assert!(pom.contains("<name>LicenseRef-Acme</name>"));
+    assert!(!pom.contains("<url>https://spdx.org/licenses/LicenseRef-Acme.html</url>"));
+
+    let path = temp_dir().join("license_meson_custom");
+    let data = TemplateData::new(&path, "meson-license").license("LicenseRef-Unknown");
+    assert!(Meson::new().create_project(data).is_err());
+}
+
+#[test]
+fn test_reuse_format() {
+    let path = temp_dir().join("license_poetry_dep5");
+    let data = TemplateData::new(&path, "poetry-license")
+        .license("MIT")
+        .author("Jane Doe <jane.doe@example.org>")
+        .year(2024)
+        .reuse_format(ReuseFormat::Dep5);
+    Poetry::new().create_project(data).unwrap();
+
+    let dep5 = read(&path.join(".reuse/dep5"));
+    assert!(dep5.contains("Copyright: 2024 Jane Doe <jane.doe@example.org>"));
+    assert!(dep5.contains("License: MIT"));
+    assert!(!path.join("REUSE.toml").exists());
+
+    let path = temp_dir().join("license_poetry_toml");
+    let data = TemplateData::new(&path, "poetry-license")
+        .license("MIT")
+        .author("Jane Doe <jane.doe@example.org>")
+        .year(2024);
+    Poetry::new().create_project(data).unwrap();
+
+    let reuse = read(&path.join("REUSE.toml"));
+    assert!(reuse.contains("SPDX-FileCopyrightText = \"2024 Jane Doe <jane.doe@example.org>\""));
+    assert!(reuse.contains("SPDX-License-Identifier = \"MIT\""));
+    assert!(!path.join(".reuse").exists());
+}
+
+#[test]
+fn test_license_copyright() {
+    let tmp_dir = temp_dir();
+
+    let path = tmp_dir.join("license_copyright");
+    let data = TemplateData::new(&path, "maven-license")
+        .author("Jane Doe <jane.doe@example.org>")
+        .year(2024);
+    Maven::new().group("POL").create_project(data).unwrap();
+    let text = read(&path.join("LICENSES/MIT.txt"));
+    assert!(text.contains("Copyright (c) 2024 Jane Doe\n"));
+
+    // Without a year, the copyright line is not half-filled
+    let path = tmp_dir.join("license_copyright_no_year");
+    let data = TemplateData::new(&path, "maven-license").author("Jane Doe <jane.doe@example.org>");
+    Maven::new().group("POL").create_project(data).unwrap();
+    let text = read(&path.join("LICENSES/MIT.txt"));
+    assert!(text.contains("Copyright (c) <year> <copyright holders>\n"));
+}
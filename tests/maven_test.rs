@@ -3,11 +3,19 @@ mod common;
 use std::env::temp_dir;
 use std::path::Path;
 
-use generate_ci::{maven::Maven, CreateProject, TemplateData};
+use generate_ci::{
+    error::Error,
+    maven::{JUnit, JavaVersion, Maven, MavenPlugin},
+    CreateProject, TemplateData,
+};
 
 use common::compare_template;
 
 const SNAPSHOT_PATH: &str = "../repositories/snapshots/maven/";
+const SNAPSHOT_PATH_M: &str = "../repositories/snapshots/maven_metadata/";
+const SNAPSHOT_PATH_P: &str = "../repositories/snapshots/maven_plugins/";
+const SNAPSHOT_PATH_MOD: &str = "../repositories/snapshots/maven_modules/";
+const SNAPSHOT_PATH_R: &str = "../repositories/snapshots/maven_release/";
 
 #[test]
 fn test_maven() {
@@ -20,3 +28,289 @@ fn test_maven() {
     Maven::new().group("POL").create_project(data).unwrap();
     compare_template(Path::new(SNAPSHOT_PATH), &path);
 }
+
+#[test]
+fn test_maven_metadata() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_metadata");
+    let data = TemplateData::new(&path, "maven-metadata")
+        .license("MIT")
+        .branch("main")
+        .description("A maven project with metadata")
+        .author("Jane Doe <jane.doe@example.org>")
+        .repository("https://github.com/example/maven-metadata")
+        .version("1.2.3");
+
+    Maven::new().group("POL").create_project(data).unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_M), &path);
+}
+
+#[test]
+fn test_maven_plugins() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_plugins");
+    let data = TemplateData::new(&path, "maven-plugins")
+        .license("MIT")
+        .branch("main");
+
+    Maven::new()
+        .group("POL")
+        .java(JavaVersion::Java11)
+        .junit(JUnit::JUnit4)
+        .plugins(&[
+            MavenPlugin::Checkstyle,
+            MavenPlugin::Spotless,
+            MavenPlugin::SpotBugs,
+            MavenPlugin::Pmd,
+            MavenPlugin::Jacoco,
+        ])
+        .coverage_threshold(80)
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_P), &path);
+}
+
+#[test]
+fn test_maven_modules() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_modules");
+    let data = TemplateData::new(&path, "maven-modules")
+        .license("MIT")
+        .branch("main");
+
+    Maven::new()
+        .group("POL")
+        .modules(&["core", "command-line"])
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_MOD), &path);
+}
+
+#[test]
+fn test_maven_release() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_release");
+    let data = TemplateData::new(&path, "maven-release")
+        .license("Apache-2.0")
+        .branch("main")
+        .author("Jane Doe <jane.doe@example.org>")
+        .repository("https://github.com/example/maven-release")
+        .year(2024);
+
+    Maven::new()
+        .group("org.example")
+        .release_repository("https://maven.example.org/releases")
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_R), &path);
+}
+
+#[test]
+fn test_maven_metadata_escaped() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_escaped");
+    let data = TemplateData::new(&path, "maven-escaped")
+        .description("Tools for <T> & friends")
+        .author("Tom & Jerry <tj@example.org>");
+
+    Maven::new()
+        .group("org.example")
+        .create_project(data)
+        .unwrap();
+
+    let pom = std::fs::read_to_string(path.join("pom.xml")).unwrap();
+    assert!(pom.contains("<description>Tools for &lt;T&gt; &amp; friends</description>"));
+    assert!(pom.contains("<name>Tom &amp; Jerry</name>"));
+    assert!(pom.contains("<email>tj@example.org</email>"));
+}
+
+#[test]
+fn test_maven_release_central() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_release_central");
+    let data = TemplateData::new(&path, "maven-release-central");
+
+    Maven::new()
+        .group("org.example")
+        .release()
+        .create_project(data)
+        .unwrap();
+
+    // Maven Central is reached through the Central Publisher Portal plugin
+    let pom = std::fs::read_to_string(path.join("pom.xml")).unwrap();
+    assert!(pom.contains("<artifactId>central-publishing-maven-plugin</artifactId>"));
+    assert!(pom.contains("<publishingServerId>release</publishingServerId>"));
+    assert!(!pom.contains("<distributionManagement>"));
+    assert!(!pom.contains("oss.sonatype.org"));
+    // Missing metadata leaves out the fields instead of placeholders
+    assert!(!pom.contains("<scm>"));
+    assert!(!pom.contains("<developers>"));
+    assert!(!pom.contains("your-account"));
+
+    let readme = std::fs::read_to_string(path.join("README.md")).unwrap();
+    assert!(readme.contains("to Maven Central through the Central Publisher Portal."));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_maven_wrapper() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_wrapper");
+    let data = TemplateData::new(&path, "maven-wrapper");
+
+    Maven::new().group("POL").create_project(data).unwrap();
+
+    let mode = std::fs::metadata(path.join("mvnw"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o111, 0o111);
+    assert!(path.join(".mvn/wrapper/maven-wrapper.properties").is_file());
+}
+
+#[test]
+fn test_maven_nested_group() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_group");
+    let data = TemplateData::new(&path, "maven-group");
+
+    Maven::new()
+        .group("com.example")
+        .create_project(data)
+        .unwrap();
+
+    let entry = path.join("src/main/java/com/example/maven_group/MavenGroup.java");
+    let content = std::fs::read_to_string(entry).unwrap();
+    assert!(content.starts_with("package com.example.maven_group;"));
+    let example = path.join("src/test/java/com/example/maven_group/example/Example.java");
+    let content = std::fs::read_to_string(example).unwrap();
+    assert!(content.starts_with("package com.example.maven_group.example;"));
+}
+
+#[test]
+fn test_maven_invalid_group() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_invalid_group");
+    let data = TemplateData::new(&path, "maven-group");
+
+    let error = Maven::new()
+        .group("com.my-org")
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidName { suggestion, .. } if suggestion == "com.my_org"
+    ));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_maven_coverage_module() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_coverage_module");
+    let data = TemplateData::new(&path, "maven-coverage");
+
+    let error = Maven::new()
+        .group("POL")
+        .modules(&["core", "coverage"])
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::InvalidName { .. }));
+    assert!(!path.exists());
+
+    // The name stays reserved without jacoco
+    let data = TemplateData::new(&path, "maven-coverage");
+    let error = Maven::new()
+        .group("POL")
+        .plugins(&[MavenPlugin::Checkstyle])
+        .modules(&["core", "coverage"])
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::InvalidName { .. }));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_maven_duplicate_modules() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_duplicate_modules");
+    let data = TemplateData::new(&path, "maven-duplicate");
+
+    let error = Maven::new()
+        .group("POL")
+        .modules(&["core", "api", "core"])
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedOption(_)));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_maven_coverage_threshold() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("maven_coverage_threshold");
+    let data = TemplateData::new(&path, "maven-threshold");
+
+    let error = Maven::new()
+        .group("POL")
+        .coverage_threshold(150)
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedOption(_)));
+    assert!(!path.exists());
+}
+
+// Checks the rules of google-java-format, with the AOSP style, followed by
+// the Java files of a project
+fn assert_formatted(path: &Path) {
+    for entry in std::fs::read_dir(path).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            assert_formatted(&path);
+            continue;
+        }
+        if path.extension().map_or(true, |ext| ext != "java") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        assert!(source.ends_with("}\n"), "{}", path.display());
+        assert!(!source.contains("\n\n\n"), "{}", path.display());
+        for line in source.lines() {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            assert_eq!(indent % 4, 0, "{}: {line}", path.display());
+            assert!(line.len() <= 100, "{}: {line}", path.display());
+            assert!(!line.contains('\t'), "{}: {line}", path.display());
+            assert_eq!(line.trim_end(), line, "{}: {line}", path.display());
+        }
+        let imports: Vec<&str> = source
+            .lines()
+            .filter(|line| line.starts_with("import ") && !line.starts_with("import static "))
+            .collect();
+        assert!(imports.is_sorted(), "{}", path.display());
+    }
+}
+
+#[test]
+fn test_maven_spotless() {
+    for (junit, group) in [
+        (JUnit::JUnit4, "com.example"),
+        (JUnit::JUnit5, "zz.example"),
+    ] {
+        let tmp_dir = temp_dir();
+        let path = tmp_dir.join(format!("maven_spotless_{group}"));
+        let data = TemplateData::new(&path, "maven-spotless");
+
+        Maven::new()
+            .group(group)
+            .junit(junit)
+            .plugins(&[MavenPlugin::Spotless])
+            .create_project(data)
+            .unwrap();
+
+        let pom = std::fs::read_to_string(path.join("pom.xml")).unwrap();
+        assert!(pom.contains("<style>AOSP</style>"));
+        assert_formatted(&path);
+    }
+}
@@ -4,14 +4,20 @@ use std::env::temp_dir;
 use std::path::Path;
 
 use generate_ci::{
-    meson::{Meson, ProjectKind},
-    CreateProject, TemplateData,
+    error::Error,
+    meson::{Layout, LibraryKind, Meson, ProjectKind, Standard, TestFramework},
+    CreateProject, ReuseFormat, TemplateData,
 };
 
 use common::compare_template;
 
 const SNAPSHOT_PATH_CPP: &str = "../repositories/snapshots/meson_cpp/";
 const SNAPSHOT_PATH_C: &str = "../repositories/snapshots/meson_c/";
+const SNAPSHOT_PATH_H: &str = "../repositories/snapshots/meson_headers/";
+const SNAPSHOT_PATH_O: &str = "../repositories/snapshots/meson_options/";
+const SNAPSHOT_PATH_UNITY: &str = "../repositories/snapshots/meson_unity/";
+const SNAPSHOT_PATH_GTEST: &str = "../repositories/snapshots/meson_gtest/";
+const SNAPSHOT_PATH_LIBRARY: &str = "../repositories/snapshots/meson_library/";
 
 #[test]
 fn test_meson_cpp() {
@@ -42,3 +48,197 @@ fn test_meson_c() {
         .unwrap();
     compare_template(Path::new(SNAPSHOT_PATH_C), &path);
 }
+
+#[test]
+fn test_meson_headers() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_headers");
+    let data = TemplateData::new(&path, "meson-headers")
+        .license("BSD-1-Clause")
+        .branch("main")
+        .author("Jane Doe <jane.doe@example.org>")
+        .year(2024)
+        .reuse_format(ReuseFormat::Dep5)
+        .spdx_headers();
+
+    Meson::new()
+        .kind(ProjectKind::C)
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_H), &path);
+}
+
+#[test]
+fn test_meson_options() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_options");
+    let data = TemplateData::new(&path, "meson-options")
+        .license("BSD-1-Clause")
+        .branch("main");
+
+    Meson::new()
+        .kind(ProjectKind::Cxx)
+        .standard(Standard::Cxx23)
+        .warning_level(2)
+        .werror()
+        .flags(&["-Wshadow", "-Wconversion"])
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_O), &path);
+}
+
+#[test]
+fn test_meson_unity() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_unity");
+    let data = TemplateData::new(&path, "meson-unity")
+        .license("BSD-1-Clause")
+        .branch("main");
+
+    Meson::new()
+        .kind(ProjectKind::C)
+        .test_framework(TestFramework::Unity)
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_UNITY), &path);
+}
+
+#[test]
+fn test_meson_gtest() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_gtest");
+    let data = TemplateData::new(&path, "meson-gtest")
+        .license("BSD-1-Clause")
+        .branch("main");
+
+    Meson::new()
+        .kind(ProjectKind::Cxx)
+        .test_framework(TestFramework::GoogleTest)
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_GTEST), &path);
+}
+
+#[test]
+fn test_meson_catch2() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_catch2");
+    let data = TemplateData::new(&path, "meson-catch2");
+
+    Meson::new()
+        .kind(ProjectKind::Cxx)
+        .test_framework(TestFramework::Catch2)
+        .create_project(data)
+        .unwrap();
+
+    assert!(path.join("subprojects/catch2.wrap").is_file());
+    let build = std::fs::read_to_string(path.join("tests/meson.build")).unwrap();
+    assert!(build.contains("dependency('catch2-with-main')"));
+    assert!(build.contains("protocol: 'tap'"));
+}
+
+#[test]
+fn test_meson_library() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_library");
+    let data = TemplateData::new(&path, "meson-library")
+        .license("BSD-1-Clause")
+        .branch("main");
+
+    Meson::new()
+        .kind(ProjectKind::C)
+        .layout(Layout::Library)
+        .library_kind(LibraryKind::Static)
+        .create_project(data)
+        .unwrap();
+    compare_template(Path::new(SNAPSHOT_PATH_LIBRARY), &path);
+    assert!(!path.join("cli").exists());
+}
+
+#[test]
+fn test_meson_executable() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_executable");
+    let data = TemplateData::new(&path, "meson-executable");
+
+    Meson::new()
+        .kind(ProjectKind::C)
+        .layout(Layout::Executable)
+        .create_project(data)
+        .unwrap();
+
+    assert!(!path.join("lib").exists());
+    let root = std::fs::read_to_string(path.join("meson.build")).unwrap();
+    assert!(root.contains("include_directories('.', 'cli')"));
+    assert!(!root.contains("subdir('lib')"));
+    let cli = std::fs::read_to_string(path.join("cli/meson.build")).unwrap();
+    assert!(!cli.contains("_dep"));
+    let tests = std::fs::read_to_string(path.join("tests/meson.build")).unwrap();
+    assert!(!tests.contains("dependencies"));
+}
+
+#[test]
+fn test_meson_library_kind_mismatch() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_library_kind_mismatch");
+    let data = TemplateData::new(&path, "meson-mismatch");
+
+    let error = Meson::new()
+        .kind(ProjectKind::C)
+        .layout(Layout::Executable)
+        .library_kind(LibraryKind::Static)
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedOption(_)));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_meson_criterion() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_criterion");
+    let data = TemplateData::new(&path, "meson-criterion");
+
+    Meson::new()
+        .kind(ProjectKind::C)
+        .test_framework(TestFramework::Criterion)
+        .create_project(data)
+        .unwrap();
+
+    assert!(path.join("subprojects/criterion.wrap").is_file());
+    let build = std::fs::read_to_string(path.join("tests/meson.build")).unwrap();
+    assert!(build.contains("dependency('criterion')"));
+    assert!(build.contains("protocol: 'tap'"));
+    let test = std::fs::read_to_string(path.join("tests/meson_criterion.c")).unwrap();
+    assert!(test.contains("Test(meson_criterion, example)"));
+}
+
+#[test]
+fn test_meson_framework_mismatch() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_framework_mismatch");
+    let data = TemplateData::new(&path, "meson-mismatch");
+
+    let error = Meson::new()
+        .kind(ProjectKind::C)
+        .test_framework(TestFramework::GoogleTest)
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedOption(_)));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_meson_standard_mismatch() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("meson_mismatch");
+    let data = TemplateData::new(&path, "meson-mismatch");
+
+    let error = Meson::new()
+        .kind(ProjectKind::C)
+        .standard(Standard::Cxx20)
+        .create_project(data)
+        .unwrap_err();
+    assert!(matches!(error, Error::UnsupportedOption(_)));
+    assert!(!path.exists());
+}
@@ -3,11 +3,12 @@ mod common;
 use std::env::temp_dir;
 use std::path::Path;
 
-use generate_ci::{poetry::Poetry, CreateProject, TemplateData};
+use generate_ci::{error::Error, poetry::Poetry, CreateProject, TemplateData};
 
 use common::compare_template;
 
 const SNAPSHOT_PATH: &str = "../repositories/snapshots/poetry/";
+const SNAPSHOT_PATH_M: &str = "../repositories/snapshots/poetry_metadata/";
 
 #[test]
 fn test_poetry() {
@@ -21,3 +22,71 @@ fn test_poetry() {
 
     compare_template(Path::new(SNAPSHOT_PATH), &path);
 }
+
+#[test]
+fn test_poetry_metadata() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("poetry_metadata");
+    let data = TemplateData::new(&path, "poetry-metadata")
+        .license("MIT")
+        .branch("main")
+        .description("A poetry project with metadata")
+        .author("Jane Doe <jane.doe@example.org>")
+        .author("John Doe")
+        .homepage("https://example.org/poetry-metadata")
+        .repository("https://github.com/example/poetry-metadata")
+        .version("1.2.3")
+        .year(2024);
+
+    Poetry::new().create_project(data).unwrap();
+
+    compare_template(Path::new(SNAPSHOT_PATH_M), &path);
+}
+
+#[test]
+fn test_poetry_metadata_escaped() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("poetry_escaped");
+    let data = TemplateData::new(&path, "poetry-escaped")
+        .description("The \"best\" tool\nfor C:\\ paths")
+        .author("Jane \"JD\" Doe <jane.doe@example.org>");
+
+    Poetry::new().create_project(data).unwrap();
+
+    // The TOML files stay valid and keep the values as given
+    let read = |file: &str| -> toml::Table {
+        std::fs::read_to_string(path.join(file))
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    let pyproject = read("pyproject.toml");
+    let poetry = &pyproject["tool"]["poetry"];
+    assert_eq!(
+        poetry["description"].as_str(),
+        Some("The \"best\" tool\nfor C:\\ paths")
+    );
+    assert_eq!(
+        poetry["authors"][0].as_str(),
+        Some("Jane \"JD\" Doe <jane.doe@example.org>")
+    );
+    let reuse = read("REUSE.toml");
+    assert_eq!(
+        reuse["annotations"][0]["SPDX-FileCopyrightText"].as_str(),
+        Some("Jane \"JD\" Doe <jane.doe@example.org>")
+    );
+}
+
+#[test]
+fn test_poetry_invalid_name() {
+    let tmp_dir = temp_dir();
+    let path = tmp_dir.join("poetry_invalid");
+    let data = TemplateData::new(&path, "3d-tools");
+
+    let error = Poetry::new().create_project(data).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidName { suggestion, .. } if suggestion == "project-3d-tools"
+    ));
+    assert!(!path.exists());
+}
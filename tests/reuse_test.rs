@@ -0,0 +1,179 @@
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read_to_string, remove_dir_all, write};
+
+use generate_ci::{
+    cargo::Cargo,
+    maven::Maven,
+    meson::{Meson, ProjectKind},
+    poetry::Poetry,
+    reuse::lint,
+    CreateCi, CreateProject, ReuseFormat, TemplateData,
+};
+
+#[test]
+fn test_reuse_generated_projects() {
+    let path = temp_dir().join("reuse_poetry");
+    let data = TemplateData::new(&path, "reuse-poetry").license("MIT OR Apache-2.0");
+    Poetry::new().create_project(data).unwrap();
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+
+    let path = temp_dir().join("reuse_meson");
+    let data = TemplateData::new(&path, "reuse-meson")
+        .license("BSD-3-Clause")
+        .reuse_format(ReuseFormat::Dep5)
+        .spdx_headers();
+    Meson::new()
+        .kind(ProjectKind::Cxx)
+        .create_project(data)
+        .unwrap();
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+
+    let path = temp_dir().join("reuse_cargo");
+    let data = TemplateData::new(&path, "reuse-cargo").spdx_headers();
+    Cargo::new().create_ci(data).unwrap();
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+}
+
+#[test]
+fn test_reuse_vendored_files() {
+    // The Maven wrapper keeps the Apache-2.0 license of its project
+    let path = temp_dir().join("reuse_maven");
+    let _ = remove_dir_all(&path);
+    let data = TemplateData::new(&path, "reuse-maven").license("MIT");
+    Maven::new()
+        .group("org.example")
+        .create_project(data)
+        .unwrap();
+    let reuse = read_to_string(path.join("REUSE.toml")).unwrap();
+    assert!(reuse.contains(
+        "path = [\n    \"mvnw\",\n    \"mvnw.cmd\",\n    \".mvn/wrapper/**\",\n]\n\
+         precedence = \"override\"\n\
+         SPDX-FileCopyrightText = \"The Apache Software Foundation\"\n\
+         SPDX-License-Identifier = \"Apache-2.0\""
+    ));
+    assert!(path.join("LICENSES/Apache-2.0.txt").is_file());
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+
+    let path = temp_dir().join("reuse_maven_dep5");
+    let _ = remove_dir_all(&path);
+    let data = TemplateData::new(&path, "reuse-maven-dep5")
+        .license("MIT")
+        .reuse_format(ReuseFormat::Dep5)
+        .spdx_headers();
+    Maven::new()
+        .group("org.example")
+        .create_project(data)
+        .unwrap();
+    let dep5 = read_to_string(path.join(".reuse/dep5")).unwrap();
+    assert!(dep5.ends_with(
+        "\n\nFiles: mvnw mvnw.cmd .mvn/wrapper/*\n\
+         Copyright: The Apache Software Foundation\n\
+         License: Apache-2.0"
+    ));
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+}
+
+#[test]
+fn test_reuse_lint() {
+    let path = temp_dir().join("reuse_lint");
+    let _ = remove_dir_all(&path);
+    create_dir_all(path.join("LICENSES")).unwrap();
+    write(path.join("LICENSES/MIT.txt"), "MIT License").unwrap();
+    write(path.join("LICENSES/BSD-3-Clause.txt"), "BSD License").unwrap();
+    write(
+        path.join("main.rs"),
+        "// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-License-Identifier: MIT\n",
+    )
+    .unwrap();
+    write(
+        path.join("lib.rs"),
+        "// SPDX-License-Identifier: Apache-2.0\n",
+    )
+    .unwrap();
+    write(path.join("data.json"), "{}").unwrap();
+    write(
+        path.join("data.json.license"),
+        "SPDX-FileCopyrightText: 2024 Jane Doe\nSPDX-License-Identifier: Foo\n",
+    )
+    .unwrap();
+    write(path.join("README.md"), "# Readme").unwrap();
+
+    let report = lint(&path).unwrap();
+    assert!(!report.is_compliant());
+    assert_eq!(report.bad_licenses().collect::<Vec<_>>(), vec!["Foo"]);
+    assert_eq!(
+        report.missing_licenses().collect::<Vec<_>>(),
+        vec!["Apache-2.0", "Foo"]
+    );
+    assert_eq!(report.unused_licenses(), ["BSD-3-Clause"]);
+    assert_eq!(report.files_without_copyright(), ["README.md", "lib.rs"]);
+    assert_eq!(report.files_without_license(), ["README.md"]);
+
+    let output = report.to_string();
+    assert!(output.contains("# MISSING LICENSES\n\n'Apache-2.0' found in:\n* lib.rs\n"));
+    assert!(output.contains("* Files with license information: 3 / 4\n"));
+    assert!(output.ends_with("not compliant with version 3.3 of the REUSE Specification :-("));
+}
+
+#[test]
+fn test_reuse_precedence() {
+    let path = temp_dir().join("reuse_precedence");
+    let _ = remove_dir_all(&path);
+    create_dir_all(path.join("LICENSES")).unwrap();
+    for id in ["MIT", "Apache-2.0", "BSD-3-Clause"] {
+        write(path.join(format!("LICENSES/{id}.txt")), id).unwrap();
+    }
+    let header = |license: &str| {
+        format!("// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-License-Identifier: {license}\n")
+    };
+    write(path.join("closest.rs"), header("Apache-2.0")).unwrap();
+    write(path.join("aggregate.rs"), header("Apache-2.0")).unwrap();
+    write(path.join("override.rs"), header("Foo")).unwrap();
+    write(
+        path.join("REUSE.toml"),
+        r#"version = 1
+
+[[annotations]]
+path = "closest.rs"
+SPDX-FileCopyrightText = "2024 John Doe"
+SPDX-License-Identifier = "LicenseRef-Unused"
+
+[[annotations]]
+path = "aggregate.rs"
+precedence = "aggregate"
+SPDX-FileCopyrightText = "2024 John Doe"
+SPDX-License-Identifier = "BSD-3-Clause"
+
+[[annotations]]
+path = "override.rs"
+precedence = "override"
+SPDX-FileCopyrightText = "2024 John Doe"
+SPDX-License-Identifier = "MIT"
+"#,
+    )
+    .unwrap();
+
+    // The header of closest.rs wins, the one of override.rs is ignored and
+    // aggregate.rs uses both licenses
+    let report = lint(&path).unwrap();
+    assert!(report.is_compliant(), "{report}");
+
+    // The specification forbids having both configuration files
+    create_dir_all(path.join(".reuse")).unwrap();
+    write(
+        path.join(".reuse/dep5"),
+        "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n",
+    )
+    .unwrap();
+    let report = lint(&path).unwrap();
+    assert!(report.dual_configuration());
+    assert!(!report.is_compliant());
+    assert!(report
+        .to_string()
+        .starts_with("# CONFLICTING CONFIGURATION\n\nBoth .reuse/dep5 and REUSE.toml exist"));
+}